@@ -0,0 +1,171 @@
+//! # ArrayDeque shifting benchmark
+//! Compare `ArrayDeque` bulk block moves against the previous implementation,
+//! which shifted elements one at a time with a modulo on every iteration.
+//!
+//! USAGE: cargo bench --bench array_deque_shift -- [LEN]
+
+use opendatastructures::array_based_lists::array_deque::ArrayDeque;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::env::args;
+use std::hint::black_box;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::time::{Duration, Instant};
+
+/// The per-element shifting ArrayDeque, kept as a baseline
+struct ModuloArrayDeque<T: Copy> {
+    buf: Box<[MaybeUninit<T>]>,
+    len: usize,
+    offset: usize,
+}
+
+impl<T: Copy> ModuloArrayDeque<T> {
+    fn new() -> Self {
+        ModuloArrayDeque {
+            buf: [].into(),
+            len: 0,
+            offset: 0,
+        }
+    }
+
+    fn add(&mut self, index: usize, element: T) {
+        let len = self.len;
+        if len == self.buf.len() {
+            self.resize();
+        }
+        let capacity = self.buf.len();
+
+        let p = self.buf.as_mut_ptr();
+        if index < len / 2 {
+            self.offset = self.offset.checked_sub(1).unwrap_or(capacity - 1);
+            for k in 0..index {
+                unsafe {
+                    ptr::write(
+                        p.add((self.offset + k) % capacity),
+                        ptr::read(p.add((self.offset + k + 1) % capacity)),
+                    );
+                }
+            }
+        } else {
+            for k in (index..len).rev() {
+                unsafe {
+                    ptr::write(
+                        p.add((self.offset + k + 1) % capacity),
+                        ptr::read(p.add((self.offset + k) % capacity)),
+                    );
+                }
+            }
+        }
+        unsafe {
+            ptr::write(
+                p.add((self.offset + index) % capacity),
+                MaybeUninit::new(element),
+            );
+        }
+        self.len += 1;
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let len = self.len;
+        if index >= len {
+            return None;
+        }
+
+        let capacity = self.buf.len();
+        let p = self.buf.as_mut_ptr();
+        let element =
+            unsafe { Some(ptr::read(p.add((self.offset + index) % capacity)).assume_init()) };
+        if index < len / 2 {
+            for k in (0..index).rev() {
+                unsafe {
+                    ptr::write(
+                        p.add((self.offset + k + 1) % capacity),
+                        ptr::read(p.add((self.offset + k) % capacity)),
+                    );
+                }
+            }
+            self.offset = (self.offset + 1) % capacity;
+        } else {
+            for k in index..len - 1 {
+                unsafe {
+                    ptr::write(
+                        p.add((self.offset + k) % capacity),
+                        ptr::read(p.add((self.offset + k + 1) % capacity)),
+                    );
+                }
+            }
+        }
+
+        self.len -= 1;
+        if capacity >= 3 * self.len {
+            self.resize();
+        }
+        element
+    }
+
+    fn resize(&mut self) {
+        let capacity = self.buf.len();
+        let new_capacity = usize::max(1, 2 * self.len);
+        let mut new_buffer = Vec::with_capacity(new_capacity);
+        new_buffer.resize_with(new_capacity, MaybeUninit::uninit);
+        for (k, slot) in new_buffer.iter_mut().take(self.len).enumerate() {
+            *slot = self.buf[(self.offset + k) % capacity];
+        }
+        self.buf = new_buffer.into_boxed_slice();
+        self.offset = 0;
+    }
+}
+
+/// Positional insertion and removal, as benchmarked
+trait Shifting {
+    fn add(&mut self, index: usize, element: u64);
+    fn remove(&mut self, index: usize) -> Option<u64>;
+}
+
+impl Shifting for ModuloArrayDeque<u64> {
+    fn add(&mut self, index: usize, element: u64) {
+        ModuloArrayDeque::add(self, index, element)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<u64> {
+        ModuloArrayDeque::remove(self, index)
+    }
+}
+
+impl Shifting for ArrayDeque<u64> {
+    fn add(&mut self, index: usize, element: u64) {
+        ArrayDeque::add(self, index, element)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<u64> {
+        ArrayDeque::remove(self, index)
+    }
+}
+
+/// Insert then remove `len` elements at random positions
+fn run(deque: &mut impl Shifting, len: usize) -> Duration {
+    let mut rng = StdRng::seed_from_u64(42);
+    let start = Instant::now();
+    for i in 0..len {
+        deque.add(rng.gen_range(0..=i), i as u64);
+    }
+    for i in (0..len).rev() {
+        black_box(deque.remove(rng.gen_range(0..=i)));
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let len = args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(50_000);
+    println!("Insert then remove {} elements at random positions", len);
+
+    let elapsed = run(&mut ModuloArrayDeque::new(), len);
+    println!("per-element modulo shifting: {:>10.3?}", elapsed);
+
+    let elapsed = run(&mut ArrayDeque::new(), len);
+    println!("ArrayDeque block moves:      {:>10.3?}", elapsed);
+}
@@ -0,0 +1,287 @@
+//! # Ring indexing benchmark
+//! Compare `ArrayDeque`, which keeps power of two capacities and maps indices with a mask,
+//! against the previous `ArrayDeque`, which mapped indices with a modulo, and against `VecDeque`.
+//!
+//! USAGE: cargo bench --bench ring_indexing -- [LEN]
+
+use opendatastructures::array_based_lists::array_deque::ArrayDeque;
+use opendatastructures::array_based_lists::resize_policy::ResizePolicy;
+use opendatastructures::deque::Deque;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::VecDeque;
+use std::env::args;
+use std::hint::black_box;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::time::{Duration, Instant};
+
+/// `ArrayDeque` as it was before power of two capacities, mapping indices with a modulo
+///
+/// Kept as a baseline: same buffer, same chunked shifts and same default resize policy,
+/// only the index mapping and the capacities differ.
+struct ModuloArrayDeque<T> {
+    buf: Box<[MaybeUninit<T>]>,
+    policy: ResizePolicy,
+    len: usize,
+    offset: usize,
+}
+
+impl<T> ModuloArrayDeque<T> {
+    fn new() -> Self {
+        ModuloArrayDeque {
+            buf: uninit_slots(0),
+            policy: ResizePolicy::default(),
+            len: 0,
+            offset: 0,
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            Some(unsafe { &*self.buf[(index + self.offset) % self.buf.len()].as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    fn add(&mut self, index: usize, element: T) {
+        let len = self.len;
+        assert!(index <= len, "index out of bounds");
+        if len == self.buf.len() {
+            let factor = self.policy.growth_factor();
+            self.resize(usize::max(1, len * factor));
+        }
+        let capacity = self.buf.len();
+        let offset = self.offset;
+
+        let p = self.buf.as_mut_ptr();
+        if index < len / 2 {
+            // shift to the left 0..index
+            self.offset = offset.checked_sub(1).unwrap_or(capacity - 1);
+            unsafe { shift_left(p, capacity, offset, index, 1) };
+        } else {
+            // shift to the right index..len
+            let start = (offset + index) % capacity;
+            unsafe { shift_right(p, capacity, start, len - index, 1) };
+        }
+        unsafe {
+            ptr::write(
+                p.add((self.offset + index) % capacity),
+                MaybeUninit::new(element),
+            );
+        }
+        self.len += 1;
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let len = self.len;
+        if index >= len {
+            return None;
+        }
+
+        let capacity = self.buf.len();
+        let offset = self.offset;
+        let p = self.buf.as_mut_ptr();
+        let element = unsafe { Some(ptr::read(p.add((offset + index) % capacity)).assume_init()) };
+        if index < len / 2 {
+            // shift to the right 0..index
+            unsafe { shift_right(p, capacity, offset, index, 1) };
+            self.offset = (offset + 1) % capacity;
+        } else {
+            // shift to the left index + 1..len
+            let start = (offset + index + 1) % capacity;
+            unsafe { shift_left(p, capacity, start, len - index - 1, 1) };
+        }
+
+        self.len -= 1;
+        if let Some(threshold) = self.policy.shrink_threshold() {
+            let shrunk = usize::max(1, self.len * self.policy.growth_factor());
+            if capacity >= self.len.saturating_mul(threshold) && shrunk < capacity {
+                self.resize(shrunk);
+            }
+        }
+        element
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        let mut buf = uninit_slots(capacity);
+        let (len, offset, old) = (self.len, self.offset, self.buf.len());
+        let src = self.buf.as_ptr();
+        unsafe {
+            if offset + len > old {
+                ptr::copy_nonoverlapping(src.add(offset), buf.as_mut_ptr(), old - offset);
+                ptr::copy_nonoverlapping(
+                    src,
+                    buf.as_mut_ptr().add(old - offset),
+                    offset + len - old,
+                );
+            } else {
+                ptr::copy_nonoverlapping(src.add(offset), buf.as_mut_ptr(), len);
+            }
+        }
+        self.buf = buf;
+        self.offset = 0;
+    }
+}
+
+impl<T> Drop for ModuloArrayDeque<T> {
+    fn drop(&mut self) {
+        while self.remove(0).is_some() {}
+    }
+}
+
+fn uninit_slots<T>(capacity: usize) -> Box<[MaybeUninit<T>]> {
+    let mut slots = Vec::with_capacity(capacity);
+    slots.resize_with(capacity, MaybeUninit::uninit);
+    slots.into_boxed_slice()
+}
+
+fn wrap(position: usize, capacity: usize) -> usize {
+    if position >= capacity {
+        position - capacity
+    } else {
+        position
+    }
+}
+
+/// Move `count` elements from `start` `distance` slots to the right, by contiguous chunks
+unsafe fn shift_right<T>(
+    buf: *mut MaybeUninit<T>,
+    capacity: usize,
+    start: usize,
+    count: usize,
+    distance: usize,
+) {
+    let mut remaining = count;
+    while remaining > 0 {
+        let src_last = wrap(start + remaining - 1, capacity);
+        let dst_last = wrap(start + remaining - 1 + distance, capacity);
+        let chunk = usize::min(remaining, usize::min(src_last, dst_last) + 1);
+        ptr::copy(
+            buf.add(src_last + 1 - chunk),
+            buf.add(dst_last + 1 - chunk),
+            chunk,
+        );
+        remaining -= chunk;
+    }
+}
+
+/// Move `count` elements from `start` `distance` slots to the left, by contiguous chunks
+unsafe fn shift_left<T>(
+    buf: *mut MaybeUninit<T>,
+    capacity: usize,
+    start: usize,
+    count: usize,
+    distance: usize,
+) {
+    let mut moved = 0;
+    while moved < count {
+        let src = wrap(start + moved, capacity);
+        let dst = wrap(src + capacity - distance, capacity);
+        let chunk = usize::min(count - moved, capacity - usize::max(src, dst));
+        ptr::copy(buf.add(src), buf.add(dst), chunk);
+        moved += chunk;
+    }
+}
+
+/// The operations being benchmarked
+trait Ring {
+    fn get(&self, index: usize) -> Option<&u64>;
+    fn add_last(&mut self, element: u64);
+    fn remove_first(&mut self) -> Option<u64>;
+}
+
+impl Ring for ModuloArrayDeque<u64> {
+    fn get(&self, index: usize) -> Option<&u64> {
+        ModuloArrayDeque::get(self, index)
+    }
+
+    fn add_last(&mut self, element: u64) {
+        self.add(self.len, element)
+    }
+
+    fn remove_first(&mut self) -> Option<u64> {
+        self.remove(0)
+    }
+}
+
+impl Ring for ArrayDeque<u64> {
+    fn get(&self, index: usize) -> Option<&u64> {
+        ArrayDeque::get(self, index)
+    }
+
+    fn add_last(&mut self, element: u64) {
+        Deque::add_last(self, element)
+    }
+
+    fn remove_first(&mut self) -> Option<u64> {
+        Deque::remove_first(self)
+    }
+}
+
+impl Ring for VecDeque<u64> {
+    fn get(&self, index: usize) -> Option<&u64> {
+        VecDeque::get(self, index)
+    }
+
+    fn add_last(&mut self, element: u64) {
+        self.push_back(element)
+    }
+
+    fn remove_first(&mut self) -> Option<u64> {
+        self.pop_front()
+    }
+}
+
+/// Rotate `len` elements through the ring, then read them at random indices
+fn run(ring: &mut impl Ring, len: usize) -> (Duration, Duration) {
+    let start = Instant::now();
+    for i in 0..len {
+        ring.add_last(i as u64);
+    }
+    for i in 0..10 * len {
+        let element = ring.remove_first().expect("ring should not be empty");
+        ring.add_last(black_box(element + i as u64));
+    }
+    let churn = start.elapsed();
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let indices: Vec<usize> = (0..10 * len).map(|_| rng.gen_range(0..len)).collect();
+    let start = Instant::now();
+    let mut sum = 0u64;
+    for &index in &indices {
+        sum = sum.wrapping_add(*ring.get(index).expect("index should be in bounds"));
+    }
+    black_box(sum);
+    (churn, start.elapsed())
+}
+
+fn main() {
+    let len = args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(1_000_000);
+    println!(
+        "Rotate {} elements 10 times, then read 10 * {} random indices",
+        len, len
+    );
+
+    let (churn, reads) = run(&mut ModuloArrayDeque::new(), len);
+    println!(
+        "ArrayDeque (mod):  churn {:>10.3?}, reads {:>10.3?}",
+        churn, reads
+    );
+
+    let (churn, reads) = run(&mut ArrayDeque::new(), len);
+    println!(
+        "ArrayDeque (mask): churn {:>10.3?}, reads {:>10.3?}",
+        churn, reads
+    );
+
+    let (churn, reads) = run(&mut VecDeque::new(), len);
+    println!(
+        "VecDeque:          churn {:>10.3?}, reads {:>10.3?}",
+        churn, reads
+    );
+}
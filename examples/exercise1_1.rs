@@ -8,13 +8,14 @@
 //! Your implementations should be fast enough that even files containing
 //! a million lines can be processed in a few seconds.
 
+use opendatastructures::array_based_lists::array_queue::RingBuffer;
 use rand::prelude::SliceRandom;
 use rand::thread_rng;
 use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
 use std::env::args;
 use std::error::Error;
 use std::fs::read_to_string;
-use std::iter::repeat;
+use std::iter::repeat_n;
 use std::process::exit;
 
 /// # Question 1.1.1
@@ -79,32 +80,25 @@ fn reverse_bulk(content: &str) {
 /// than 43 lines of the input at any given time.
 ///
 /// # Solution
-/// Fill a FIFO data structure (here a `VecDeque`) with the 42 first lines
-/// then pop_front the default entry and select it if current line is empty.
-/// Finally push_back the displayed line.
+/// Keep the 42 last displayed lines in a bounded FIFO (`RingBuffer`), evicting the oldest
+/// one on each insertion, and select the oldest one if current line is empty.
 fn fill_blanks(content: &str) {
     const BUFFER_SIZE: usize = 42;
     println!(
-        "Replace blanks lines (after line {size}) with the one {size} lines before using a Deque (VecDeque)",
+        "Replace blanks lines (after line {size}) with the one {size} lines before using a bounded Queue (RingBuffer)",
         size = BUFFER_SIZE
     );
-    let mut buffer = VecDeque::with_capacity(BUFFER_SIZE);
-    let original_capacity = buffer.capacity();
-    let lines = &mut content.lines();
+    let mut buffer = RingBuffer::with_capacity(BUFFER_SIZE);
 
-    // Fill the buffer
-    for (i, line) in lines.take(BUFFER_SIZE).enumerate() {
-        buffer.push_back(line);
+    for (i, line) in content.lines().enumerate() {
+        let line = match buffer.get_newest(BUFFER_SIZE - 1) {
+            Some(&previous) if line.is_empty() => previous,
+            _ => line,
+        };
+        buffer.add(line);
         display(i, line);
     }
-    // Run with blank detection
-    for (i, line) in lines.enumerate() {
-        let default = buffer.pop_front().expect("buffer is empty");
-        let line = if line.is_empty() { default } else { line };
-        buffer.push_back(line);
-        display(i + BUFFER_SIZE, line);
-    }
-    assert_eq!(buffer.capacity(), original_capacity);
+    assert_eq!(buffer.capacity(), BUFFER_SIZE);
 }
 
 /// # Question 1.1.4
@@ -183,7 +177,7 @@ fn sort_by_length_all(content: &str) {
     }
     for (i, line) in lines
         .into_iter()
-        .flat_map(|((_, line), count)| repeat(line).take(count))
+        .flat_map(|((_, line), count)| repeat_n(line, count))
         .enumerate()
     {
         display(i, line);
@@ -204,7 +198,7 @@ fn even_then_odd(content: &str) {
         display(i, line);
     }
     for (i, line) in lines.iter().skip(1).step_by(2).enumerate() {
-        display(i + (lines.len() + 1) / 2, line);
+        display(i + lines.len().div_ceil(2), line);
     }
 }
 
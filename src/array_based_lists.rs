@@ -2,7 +2,50 @@
 //!
 //! See Chapter 2
 
+use core::ops::{Bound, Range, RangeBounds};
+
 pub mod array_deque;
 pub mod array_queue;
 pub mod array_stack;
 pub mod dual_array_deque;
+pub mod inline_array_queue;
+pub mod inline_array_stack;
+pub mod min_deque;
+pub mod min_queue;
+pub mod min_stack;
+pub mod random_queue;
+pub(crate) mod raw_buf;
+pub mod resize_policy;
+pub(crate) mod ring;
+pub mod rootish_array_stack;
+#[cfg(test)]
+mod test_utils;
+pub mod treque;
+
+/// Resolve `range` against a list of given length
+///
+/// # Panics
+/// Panics if the range is decreasing or ends after `len`
+fn range_of<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .expect("range start index should not overflow"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .expect("range end index should not overflow"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if start > end {
+        panic!("range start (is {}) should be <= end (is {})", start, end);
+    }
+    if end > len {
+        panic!("range end (is {}) should be <= len (is {})", end, len);
+    }
+    start..end
+}
@@ -2,12 +2,28 @@
 //!
 //! See Chapter 2.4
 
-use std::mem::MaybeUninit;
-use std::ptr;
+use crate::array_based_lists::raw_buf::RawBuf;
+use crate::array_based_lists::resize_policy::ResizePolicy;
+use crate::array_based_lists::{range_of, ring};
+use crate::deque::Deque;
+use crate::error::{InsertError, OdsError};
+use crate::list::List;
+use crate::queue::Queue;
+use crate::stack::Stack;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Index, IndexMut, RangeBounds};
+use core::ptr;
+
+pub use crate::array_based_lists::ring::{Drain, IntoIter, Iter, IterMut};
 
 /// ArrayDeque: Fast Deque Operations Using an Array
 pub struct ArrayDeque<T> {
-    buf: Box<[MaybeUninit<T>]>,
+    buf: RawBuf<T>,
     len: usize,
     offset: usize,
 }
@@ -15,7 +31,16 @@ pub struct ArrayDeque<T> {
 impl<T> ArrayDeque<T> {
     pub fn new() -> Self {
         ArrayDeque {
-            buf: [].into(),
+            buf: RawBuf::new(),
+            len: 0,
+            offset: 0,
+        }
+    }
+
+    /// Create a new ArrayDeque able to hold `capacity` elements without reallocating
+    pub fn with_capacity(capacity: usize) -> Self {
+        ArrayDeque {
+            buf: RawBuf::with_capacity(ring::capacity(capacity)),
             len: 0,
             offset: 0,
         }
@@ -24,7 +49,7 @@ impl<T> ArrayDeque<T> {
     pub fn get(&self, index: usize) -> Option<&T> {
         let len = self.len;
         if index < len {
-            Some(unsafe { &*self.buf[(index + self.offset) % self.buf.len()].as_ptr() })
+            Some(unsafe { &*self.buf[ring::mask(index + self.offset, self.buf.len())].as_ptr() })
         } else {
             None
         }
@@ -33,7 +58,8 @@ impl<T> ArrayDeque<T> {
     pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
         let len = self.len;
         if index < len {
-            Some(unsafe { &mut *self.buf[(index + self.offset) % self.buf.len()].as_mut_ptr() })
+            let position = ring::mask(index + self.offset, self.buf.len());
+            Some(unsafe { &mut *self.buf[position].as_mut_ptr() })
         } else {
             None
         }
@@ -53,43 +79,74 @@ impl<T> ArrayDeque<T> {
             assert_failed(index, len);
         }
 
-        if len == self.buf.len() {
-            self.resize();
+        if len == self.buf.capacity() {
+            self.resize(self.buf.policy().grown_capacity(len));
         }
         let capacity = self.buf.len();
+        let offset = self.offset;
 
         let p = self.buf.as_mut_ptr();
         if index < len / 2 {
             // shift to the left 0..index
-            self.offset = self.offset.checked_sub(1).unwrap_or(capacity - 1);
-            for k in 0..index {
-                unsafe {
-                    ptr::write(
-                        p.add((self.offset + k) % capacity),
-                        ptr::read(p.add((self.offset + k + 1) % capacity)),
-                    );
-                }
-            }
+            self.offset = offset.checked_sub(1).unwrap_or(capacity - 1);
+            unsafe { ring::shift_left(p, capacity, offset, index, 1) };
         } else {
             // shift to the right index..len
-            for k in (index..len).rev() {
-                unsafe {
-                    ptr::write(
-                        p.add((self.offset + k + 1) % capacity),
-                        ptr::read(p.add((self.offset + k) % capacity)),
-                    );
-                }
-            }
+            let start = ring::mask(offset + index, capacity);
+            unsafe { ring::shift_right(p, capacity, start, len - index, 1) };
         }
         unsafe {
             ptr::write(
-                p.add((self.offset + index) % capacity),
+                p.add(ring::mask(self.offset + index, capacity)),
                 MaybeUninit::new(element),
             );
         }
         self.len += 1;
     }
 
+    /// Insert all the elements of `iter` at given index, shifting the elements on the shorter side
+    /// of `index` only once
+    ///
+    /// The buffer is grown once using the iterator lower size hint. Elements beyond that hint
+    /// are collected first, then inserted with a second shift.
+    ///
+    /// # Panics
+    /// Panics if `index` > `len`
+    pub fn add_all<I: IntoIterator<Item = T>>(&mut self, index: usize, iter: I) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+        let len = self.len;
+        if index > len {
+            assert_failed(index, len);
+        }
+
+        let mut iter = iter.into_iter();
+        let additional = iter.size_hint().0;
+        let rest: Vec<T> = if additional == 0 {
+            iter.collect()
+        } else {
+            self.reserve(additional);
+            let mut gap = Gap::open(self, index, additional);
+            for element in iter.by_ref().take(additional) {
+                gap.write(element);
+            }
+            if gap.is_full() {
+                iter.collect()
+            } else {
+                Vec::new()
+            }
+        };
+        if !rest.is_empty() {
+            self.add_all(index + additional, rest);
+        }
+    }
+
     pub fn remove(&mut self, index: usize) -> Option<T> {
         let len = self.len;
         if index >= len {
@@ -97,67 +154,397 @@ impl<T> ArrayDeque<T> {
         }
 
         let capacity = self.buf.len();
+        let offset = self.offset;
         let p = self.buf.as_mut_ptr();
         let element =
-            unsafe { Some(ptr::read(p.add((self.offset + index) % capacity)).assume_init()) };
+            unsafe { Some(ptr::read(p.add(ring::mask(offset + index, capacity))).assume_init()) };
         if index < len / 2 {
             // shift to the right 0..index
-            for k in (0..index).rev() {
-                unsafe {
-                    ptr::write(
-                        p.add((self.offset + k + 1) % capacity),
-                        ptr::read(p.add((self.offset + k) % capacity)),
-                    );
-                }
-            }
-            self.offset = (self.offset + 1) % capacity;
+            unsafe { ring::shift_right(p, capacity, offset, index, 1) };
+            self.offset = ring::mask(offset + 1, capacity);
         } else {
             // shift to the left index + 1..len
-            for k in index + 1..len {
-                unsafe {
-                    ptr::write(
-                        p.add((self.offset + k) % capacity),
-                        ptr::read(p.add((self.offset + k + 1) % capacity)),
-                    );
-                }
-            }
+            let start = ring::mask(offset + index + 1, capacity);
+            unsafe { ring::shift_left(p, capacity, start, len - index - 1, 1) };
         }
 
         self.len -= 1;
-        if capacity >= 3 * self.len {
-            self.resize();
-        }
+        self.shrink();
         element
     }
 
-    fn resize(&mut self) {
+    /// Insert given element at given index, shifting the elements on the shorter side of `index`
+    ///
+    /// Hands the element back if `index` > `len` or if the backing array cannot grow.
+    pub fn try_add(&mut self, index: usize, element: T) -> Result<(), InsertError<T>> {
         let len = self.len;
-        let offset = self.offset;
-        let old_capacity = self.buf.len();
-        let new_capacity = usize::max(1, 2 * len);
-        let mut new_buffer = Vec::with_capacity(new_capacity);
-        new_buffer.resize_with(new_capacity, MaybeUninit::uninit);
-        let mut new_buffer = new_buffer.into_boxed_slice();
+        if index > len {
+            let error = OdsError::IndexOutOfBounds { index, len };
+            return Err(InsertError::new(error, element));
+        }
+        if let Err(error) = self.try_reserve(1) {
+            return Err(InsertError::new(error, element));
+        }
+        self.add(index, element);
+        Ok(())
+    }
+
+    /// Remove and return the element at given index, shifting the elements on the shorter side
+    /// of `index`
+    ///
+    /// Fails if `index` >= `len`
+    pub fn try_remove(&mut self, index: usize) -> Result<T, OdsError> {
+        let len = self.len;
+        self.remove(index)
+            .ok_or(OdsError::IndexOutOfBounds { index, len })
+    }
+
+    /// Insert given element at the back of the deque
+    ///
+    /// Hands the element back if the backing array cannot grow.
+    pub fn try_push(&mut self, element: T) -> Result<(), InsertError<T>> {
+        self.try_add(self.len, element)
+    }
+
+    /// Iterate over references to the elements, from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(unsafe { ring::as_slices(&self.buf, self.offset, self.len) })
+    }
+
+    /// Iterate over mutable references to the elements, from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(unsafe { ring::as_mut_slices(&mut self.buf, self.offset, self.len) })
+    }
+
+    /// Rotate the deque so that the element at index `i` moves to index `(i + r) % len`
+    ///
+    /// Moves `min(r, len - r)` elements, and none if the deque is full.
+    pub fn rotate(&mut self, r: usize) {
+        self.offset = ring::rotate(&mut self.buf, self.offset, self.len, r);
+    }
+
+    /// The two contiguous segments holding the elements, from front to back
+    ///
+    /// The second one is empty if the elements do not wrap around the end of the buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        unsafe { ring::as_slices(&self.buf, self.offset, self.len) }
+    }
+
+    /// The two contiguous segments holding the elements, from front to back
+    ///
+    /// The second one is empty if the elements do not wrap around the end of the buffer.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        unsafe { ring::as_mut_slices(&mut self.buf, self.offset, self.len) }
+    }
+
+    /// Move the elements in a single segment of the buffer, without reallocating,
+    /// and return it
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.offset = ring::make_contiguous(&mut self.buf, self.offset, self.len);
+        unsafe { ring::as_mut_slices(&mut self.buf, self.offset, self.len).0 }
+    }
+
+    /// Drop all the elements, keeping the allocated capacity
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keep the first `len` elements and drop the others, keeping the allocated capacity
+    ///
+    /// Has no effect if `len` is greater than the deque length.
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len;
+        if len >= old_len {
+            return;
+        }
+        self.len = len;
         unsafe {
-            if offset + len > old_capacity {
-                // Copy offset..old_capacity
-                let src = self.buf.as_ptr().add(self.offset);
-                let dst = new_buffer.as_mut_ptr();
-                ptr::copy_nonoverlapping(src, dst, old_capacity - offset);
-                // Copy 0..(offset + len - old_capacity)
-                let src = self.buf.as_ptr();
-                let dst = new_buffer.as_mut_ptr().add(old_capacity - offset);
-                ptr::copy_nonoverlapping(src, dst, offset + len - old_capacity);
-            } else {
-                // Copy offset..offset + len
-                let src = self.buf.as_ptr().add(self.offset);
-                let dst = new_buffer.as_mut_ptr();
-                ptr::copy_nonoverlapping(src, dst, len);
-            }
+            let start = ring::mask(self.offset + len, self.buf.len());
+            let (front, back) = ring::as_mut_slices(&mut self.buf, start, old_len - len);
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+
+    /// Remove the elements in given range, yielding them in order
+    ///
+    /// The allocated capacity is kept.
+    /// The elements that are not consumed are dropped with the iterator.
+    ///
+    /// # Panics
+    /// Panics if the range is decreasing or ends after `len`
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let range = range_of(range, self.len);
+        Drain::new(&mut self.buf, &mut self.offset, &mut self.len, range)
+    }
+
+    /// The number of elements the deque can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Make room for at least `additional` more elements
+    ///
+    /// # Panics
+    /// Panics if the new capacity overflows `usize`
+    pub fn reserve(&mut self, additional: usize) {
+        let len = self.len;
+        let required = len.checked_add(additional).expect("capacity overflow");
+        if required > self.buf.capacity() {
+            let grown = self.buf.policy().grown_capacity(len);
+            self.resize(usize::max(required, grown));
         }
-        self.buf = new_buffer;
+    }
+
+    /// Make room for at least `additional` more elements
+    ///
+    /// Fails instead of panicking or aborting if the new buffer cannot be allocated.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), OdsError> {
+        let len = self.len;
+        let required = len
+            .checked_add(additional)
+            .ok_or_else(OdsError::capacity_overflow::<T>)?;
+        if required > self.buf.capacity() {
+            let grown = self.buf.policy().try_grown_capacity(len).unwrap_or(0);
+            self.try_resize(usize::max(required, grown))?;
+        }
+        Ok(())
+    }
+
+    /// Release the unused capacity
+    pub fn shrink_to_fit(&mut self) {
+        if self.buf.capacity() > self.len {
+            self.resize(self.len);
+        }
+    }
+
+    /// The policy used to grow and shrink the backing array
+    pub fn resize_policy(&self) -> ResizePolicy {
+        self.buf.policy()
+    }
+
+    /// Change the policy used to grow and shrink the backing array
+    ///
+    /// Takes effect from the next resize.
+    pub fn set_resize_policy(&mut self, policy: ResizePolicy) {
+        self.buf.set_policy(policy);
+    }
+
+    /// Move the elements to a new buffer able to hold `capacity` elements, rounded up to a power of two
+    #[cold]
+    #[inline(never)]
+    fn resize(&mut self, capacity: usize) {
+        let capacity = ring::capacity(capacity);
+        if capacity == self.buf.capacity() {
+            return;
+        }
+        unsafe { self.buf.reallocate(self.offset, self.len, capacity) };
         self.offset = 0;
     }
+
+    /// Move the elements to a new buffer able to hold `capacity` elements, rounded up to a power of two,
+    /// if it can be allocated
+    #[cold]
+    #[inline(never)]
+    fn try_resize(&mut self, capacity: usize) -> Result<(), OdsError> {
+        let capacity = ring::try_capacity(capacity).ok_or_else(OdsError::capacity_overflow::<T>)?;
+        if capacity == self.buf.capacity() {
+            return Ok(());
+        }
+        unsafe { self.buf.try_reallocate(self.offset, self.len, capacity)? };
+        self.offset = 0;
+        Ok(())
+    }
+
+    /// Shrink the backing buffer if it is too sparse
+    ///
+    /// Shrinking is best effort: the buffer is kept if a smaller one cannot be allocated.
+    fn shrink(&mut self) {
+        let capacity = self.buf.capacity();
+        if let Some(capacity) = self.buf.policy().shrunk_capacity(capacity, self.len) {
+            let _ = self.try_resize(capacity);
+        }
+    }
+}
+
+/// A gap of uninitialized slots opened by `add_all` at some index of the deque
+///
+/// Dropping it closes the remaining slots by moving back the side shifted when opening it,
+/// even if the iterator panicked.
+struct Gap<'a, T> {
+    deque: &'a mut ArrayDeque<T>,
+    index: usize,
+    end: usize,
+    tail: usize,
+    front: bool,
+}
+
+impl<'a, T> Gap<'a, T> {
+    /// Open `len` slots at `index`, moving the shorter side of the deque
+    fn open(deque: &'a mut ArrayDeque<T>, index: usize, len: usize) -> Self {
+        debug_assert!(deque.len + len <= deque.buf.capacity());
+        let capacity = deque.buf.len();
+        let offset = deque.offset;
+        let tail = deque.len - index;
+        let front = index < deque.len / 2;
+        let p = deque.buf.as_mut_ptr();
+        if front {
+            // shift to the left 0..index
+            deque.offset = ring::mask(offset + capacity - len, capacity);
+            unsafe { ring::shift_left(p, capacity, offset, index, len) };
+        } else {
+            // shift to the right index..len
+            let start = ring::mask(offset + index, capacity);
+            unsafe { ring::shift_right(p, capacity, start, tail, len) };
+        }
+        deque.len = index;
+        Gap {
+            deque,
+            index,
+            end: index + len,
+            tail,
+            front,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.index == self.end
+    }
+
+    fn write(&mut self, element: T) {
+        debug_assert!(!self.is_full());
+        let capacity = self.deque.buf.len();
+        let position = ring::mask(self.deque.offset + self.index, capacity);
+        self.deque.buf[position] = MaybeUninit::new(element);
+        self.index += 1;
+    }
+}
+
+impl<T> Drop for Gap<'_, T> {
+    fn drop(&mut self) {
+        let capacity = self.deque.buf.len();
+        let offset = self.deque.offset;
+        let remaining = self.end - self.index;
+        let p = self.deque.buf.as_mut_ptr();
+        if self.front {
+            unsafe { ring::shift_right(p, capacity, offset, self.index, remaining) };
+            self.deque.offset = ring::mask(offset + remaining, capacity);
+        } else {
+            let start = ring::mask(offset + self.end, capacity);
+            unsafe { ring::shift_left(p, capacity, start, self.tail, remaining) };
+        }
+        self.deque.len = self.index + self.tail;
+    }
+}
+
+impl<T> IntoIterator for ArrayDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        let buf = mem::replace(&mut self.buf, RawBuf::new());
+        let len = mem::replace(&mut self.len, 0);
+        let offset = mem::replace(&mut self.offset, 0);
+        IntoIter::new(buf, offset, len)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for ArrayDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut arr = Self::new();
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T> Extend<T> for ArrayDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.add(self.len, element);
+        }
+    }
+}
+
+impl<T: Clone> Clone for ArrayDeque<T> {
+    fn clone(&self) -> Self {
+        let mut arr = Self::with_capacity(self.len);
+        arr.set_resize_policy(self.resize_policy());
+        arr.extend(self.iter().cloned());
+        arr
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArrayDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArrayDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ArrayDeque<T> {}
+
+impl<T: PartialOrd> PartialOrd for ArrayDeque<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for ArrayDeque<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for ArrayDeque<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.iter().for_each(|element| element.hash(state));
+    }
+}
+
+impl<T> Index<usize> for ArrayDeque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len;
+        match self.get(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for ArrayDeque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
 }
 
 impl<T> Default for ArrayDeque<T> {
@@ -168,15 +555,116 @@ impl<T> Default for ArrayDeque<T> {
 
 impl<T> Drop for ArrayDeque<T> {
     fn drop(&mut self) {
-        for i in (0..self.len).rev() {
-            self.remove(i);
+        self.clear();
+    }
+}
+
+impl<T> List for ArrayDeque<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        ArrayDeque::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        ArrayDeque::get_mut(self, index)
+    }
+
+    fn add(&mut self, index: usize, item: T) {
+        ArrayDeque::add(self, index, item)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        ArrayDeque::remove(self, index)
+    }
+}
+
+impl<T> Deque for ArrayDeque<T> {
+    type Item = T;
+
+    fn add_first(&mut self, item: T) {
+        self.add(0, item)
+    }
+
+    fn add_last(&mut self, item: T) {
+        self.add(self.len, item)
+    }
+
+    fn remove_first(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn remove_last(&mut self) -> Option<T> {
+        let len = self.len;
+        if len > 0 {
+            self.remove(len - 1)
+        } else {
+            None
         }
     }
+
+    fn peek_first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    fn peek_last(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|index| self.get(index))
+    }
+}
+
+impl<T> Stack for ArrayDeque<T> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        self.add_last(item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.remove_last()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_last()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T> Queue for ArrayDeque<T> {
+    type Item = T;
+
+    fn add(&mut self, item: T) {
+        self.add_last(item)
+    }
+
+    fn remove(&mut self) -> Option<T> {
+        self.remove_first()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_first()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::ArrayDeque;
+    use crate::array_based_lists::resize_policy::ResizePolicy;
+    use crate::array_based_lists::test_utils;
+    use crate::deque::Deque;
+    use crate::error::OdsError;
+    use crate::queue::Queue;
+    use crate::stack::Stack;
     use std::collections::VecDeque;
 
     #[test]
@@ -191,6 +679,20 @@ mod tests {
         assert_eq!(arr.remove(0), None);
     }
 
+    #[test]
+    fn should_remove_elements_in_the_back_half() {
+        let mut arr = ArrayDeque::new();
+        arr.add(0, "foo");
+        arr.add(1, "bar");
+        arr.add(2, "foobar");
+        arr.add(3, "baz");
+        assert_eq!(arr.remove(2), Some("foobar"));
+        assert_eq!(arr.get(0), Some(&"foo"));
+        assert_eq!(arr.get(1), Some(&"bar"));
+        assert_eq!(arr.get(2), Some(&"baz"));
+        assert_eq!(arr.get(3), None);
+    }
+
     #[test]
     fn should_reallocate_properly() {
         // Compare to reference impl: VecDeque used as a FIFO
@@ -215,6 +717,353 @@ mod tests {
         assert_eq!(deque.remove(0), None);
     }
 
+    #[test]
+    fn should_add_and_remove_at_both_ends() {
+        let mut arr = ArrayDeque::new();
+        let mut deque = VecDeque::new();
+        assert_eq!(arr.peek_first(), None);
+        assert_eq!(arr.peek_last(), None);
+        for i in 0..50 {
+            if i % 3 == 0 {
+                arr.add_first(i);
+                deque.push_front(i);
+            } else {
+                arr.add_last(i);
+                deque.push_back(i);
+            }
+            assert_eq!(arr.peek_first(), deque.front());
+            assert_eq!(arr.peek_last(), deque.back());
+        }
+        for i in 0..50 {
+            if i % 2 == 0 {
+                assert_eq!(arr.remove_first(), deque.pop_front());
+            } else {
+                assert_eq!(arr.remove_last(), deque.pop_back());
+            }
+            assert_eq!(arr.peek_first(), deque.front());
+            assert_eq!(arr.peek_last(), deque.back());
+        }
+        assert_eq!(arr.remove_first(), None);
+        assert_eq!(arr.remove_last(), None);
+    }
+
+    #[test]
+    fn should_be_a_stack() {
+        let mut arr = ArrayDeque::new();
+        arr.push("foo");
+        arr.push("bar");
+        assert_eq!(arr.pop(), Some("bar"));
+        assert_eq!(arr.pop(), Some("foo"));
+        assert_eq!(arr.pop(), None);
+    }
+
+    #[test]
+    fn should_be_a_queue() {
+        let mut arr = ArrayDeque::new();
+        Queue::add(&mut arr, "foo");
+        Queue::add(&mut arr, "bar");
+        assert_eq!(Queue::remove(&mut arr), Some("foo"));
+        assert_eq!(Queue::remove(&mut arr), Some("bar"));
+        assert_eq!(Queue::remove(&mut arr), None);
+    }
+
+    #[test]
+    fn should_iterate_over_elements() {
+        let (mut arr, mut deque) = wrapped_ring();
+        assert!(arr.iter().eq(deque.iter()));
+        for element in &mut arr {
+            *element *= 2;
+        }
+        for element in deque.iter_mut() {
+            *element *= 2;
+        }
+        assert!((&arr).into_iter().eq(deque.iter()));
+        assert!(arr.into_iter().eq(deque));
+    }
+
+    #[test]
+    fn should_collect_and_iterate_from_both_ends() {
+        let arr: ArrayDeque<_> = (0..10).collect();
+        let mut iter = arr.into_iter();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(9));
+        assert_eq!(iter.len(), 8);
+        assert!(iter.eq(1..9));
+    }
+
+    /// Build a ring wrapping around the end of its buffer, along with its reference
+    fn wrapped_ring() -> (ArrayDeque<i32>, VecDeque<i32>) {
+        let (arr, deque) = test_utils::wrapped_ring::<ArrayDeque<_>>();
+        assert!(arr.offset + arr.len > arr.buf.len());
+        (arr, deque)
+    }
+
+    #[test]
+    fn should_rotate_elements() {
+        for r in 0..30 {
+            let (mut arr, mut deque) = wrapped_ring();
+            arr.rotate(r);
+            deque.rotate_right(r % deque.len());
+            assert!(arr.iter().eq(deque.iter()));
+        }
+        let mut arr: ArrayDeque<_> = (0..16).collect();
+        arr.rotate(3);
+        assert_eq!(arr.capacity(), 16);
+        assert!(arr.iter().copied().eq((13..16).chain(0..13)));
+    }
+
+    #[test]
+    fn should_expose_slices() {
+        let (mut arr, deque) = wrapped_ring();
+        let (front, back) = arr.as_slices();
+        assert_eq!((front.len(), back.len()), (11, 4));
+        assert!(front.iter().chain(back).eq(deque.iter()));
+        let (front, back) = arr.as_mut_slices();
+        front[0] = 100;
+        back[0] = 200;
+        assert_eq!(arr.iter().next(), Some(&100));
+        assert_eq!(arr.iter().nth(11), Some(&200));
+    }
+
+    #[test]
+    fn should_make_elements_contiguous() {
+        let (mut arr, mut deque) = wrapped_ring();
+        let capacity = arr.capacity();
+        arr.make_contiguous().reverse();
+        deque.make_contiguous().reverse();
+        assert!(arr.iter().eq(deque.iter()));
+        assert!(arr.as_slices().1.is_empty());
+        assert_eq!(arr.capacity(), capacity);
+
+        arr.make_contiguous().sort_unstable();
+        assert_eq!(arr.make_contiguous().binary_search(&13), Ok(8));
+        assert!(arr.iter().copied().eq(5..20));
+    }
+
+    #[test]
+    fn should_clear_and_truncate_elements() {
+        let (mut arr, mut deque) = wrapped_ring();
+        let capacity = arr.buf.len();
+        arr.truncate(20);
+        assert_eq!(arr.len, deque.len());
+        arr.truncate(11);
+        deque.truncate(11);
+        assert!(arr.iter().eq(deque.iter()));
+        arr.truncate(4);
+        deque.truncate(4);
+        assert!(arr.iter().eq(deque.iter()));
+        arr.clear();
+        assert_eq!(arr.len, 0);
+        assert_eq!(arr.buf.len(), capacity);
+    }
+
+    #[test]
+    fn should_drain_elements() {
+        for start in 0..15 {
+            for end in start..15 {
+                let (mut arr, mut deque) = wrapped_ring();
+                assert!(arr.drain(start..end).eq(deque.drain(start..end)));
+                assert!(arr.iter().eq(deque.iter()));
+                // The ring is still usable
+                arr.extend(20..30);
+                deque.extend(20..30);
+                assert!(arr.iter().eq(deque.iter()));
+            }
+        }
+        let (mut arr, mut deque) = wrapped_ring();
+        let mut drain = arr.drain(2..=9);
+        assert_eq!(drain.len(), 8);
+        assert_eq!(drain.next_back(), deque.get(9).copied());
+        assert_eq!(drain.next(), deque.get(2).copied());
+        drop(drain);
+        deque.drain(2..=9);
+        assert!(arr.iter().eq(deque.iter()));
+        assert!(arr.drain(..).eq(deque.drain(..)));
+        assert_eq!(arr.len, 0);
+    }
+
+    struct LyingIter<I> {
+        inner: I,
+        hint: usize,
+    }
+
+    impl<I: Iterator> Iterator for LyingIter<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<I::Item> {
+            self.inner.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.hint, None)
+        }
+    }
+
+    #[test]
+    fn should_add_all_elements() {
+        for index in 0..=12 {
+            for additional in 0..20 {
+                let (mut arr, mut deque) = wrapped_ring();
+                let mut back = deque.split_off(index);
+                arr.add_all(index, 100..100 + additional);
+                deque.extend(100..100 + additional);
+                deque.append(&mut back);
+                assert!(arr.iter().eq(deque.iter()));
+            }
+        }
+    }
+
+    #[test]
+    fn should_resize_once_when_adding_all_elements() {
+        let mut arr: ArrayDeque<_> = (0..16).collect();
+        arr.add_all(8, 100..150);
+        assert_eq!(arr.capacity(), 128);
+        assert!(arr.iter().copied().eq((0..8).chain(100..150).chain(8..16)));
+    }
+
+    #[test]
+    fn should_add_all_elements_of_lying_iterators() {
+        for hint in [0, 3, 5, 8, 100] {
+            let mut arr: ArrayDeque<_> = (0..10).collect();
+            let iter = LyingIter {
+                inner: 100..105,
+                hint,
+            };
+            arr.add_all(3, iter);
+            assert!(arr.iter().copied().eq((0..3).chain(100..105).chain(3..10)));
+        }
+    }
+
+    #[test]
+    fn should_keep_elements_when_adding_all_panics() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        for index in [2, 8] {
+            let mut arr: ArrayDeque<_> = (0..10).map(|_| Rc::clone(&watcher)).collect();
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                arr.add_all(
+                    index,
+                    (0..5).map(|i| {
+                        assert!(i < 3, "iterator panicked");
+                        Rc::clone(&watcher)
+                    }),
+                )
+            }));
+            assert!(result.is_err());
+            assert_eq!(arr.len, 13);
+            assert_eq!(Rc::strong_count(&watcher), 14);
+            drop(arr);
+            assert_eq!(Rc::strong_count(&watcher), 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 11) should be <= len (is 10)")]
+    fn should_panic_when_adding_all_out_of_bounds() {
+        let mut arr: ArrayDeque<_> = (0..10).collect();
+        arr.add_all(11, 0..3);
+    }
+
+    #[test]
+    fn should_manage_capacity() {
+        // Capacities are rounded up to powers of two
+        let mut arr = ArrayDeque::with_capacity(10);
+        assert_eq!(arr.capacity(), 16);
+        for i in 0..16 {
+            arr.add(arr.len, i);
+        }
+        assert_eq!(arr.capacity(), 16);
+        arr.reserve(5);
+        assert_eq!(arr.capacity(), 32);
+        arr.reserve(100);
+        assert_eq!(arr.capacity(), 128);
+        arr.shrink_to_fit();
+        assert_eq!(arr.capacity(), 16);
+        assert!(arr.iter().copied().eq(0..16));
+        arr.truncate(10);
+        assert!(arr.iter().copied().eq(0..10));
+    }
+
+    #[test]
+    fn should_try_to_add_and_remove_elements() {
+        let mut arr = ArrayDeque::new();
+        arr.try_reserve(10).unwrap();
+        assert_eq!(arr.capacity(), 16);
+        for i in 0..8 {
+            arr.try_push(i).unwrap();
+        }
+        arr.try_add(0, 10).unwrap();
+        let error = arr.try_add(10, 20).unwrap_err();
+        assert_eq!(
+            error.error(),
+            OdsError::IndexOutOfBounds { index: 10, len: 9 }
+        );
+        assert_eq!(error.into_element(), 20);
+        assert_eq!(arr.try_remove(0), Ok(10));
+        assert_eq!(
+            arr.try_remove(8),
+            Err(OdsError::IndexOutOfBounds { index: 8, len: 8 })
+        );
+        assert!(arr.iter().copied().eq(0..8));
+        assert_eq!(
+            arr.try_reserve(usize::MAX),
+            Err(OdsError::capacity_overflow::<i32>())
+        );
+        let mut arr = ArrayDeque::<u8>::new();
+        assert_eq!(
+            arr.try_reserve(1 << (usize::BITS - 2)),
+            Err(OdsError::AllocationFailed)
+        );
+    }
+
+    #[test]
+    fn should_follow_resize_policy() {
+        let mut arr = ArrayDeque::new();
+        arr.set_resize_policy(ResizePolicy::new(4, Some(8)));
+        for i in 0..5 {
+            arr.add(arr.len, i);
+        }
+        // 1 -> 4 -> 16
+        assert_eq!(arr.capacity(), 16);
+        arr.remove(0);
+        arr.remove(0);
+        arr.remove(0);
+        assert_eq!(arr.capacity(), 8);
+
+        arr.set_resize_policy(ResizePolicy::default().without_shrinking());
+        arr.reserve(100);
+        let capacity = arr.capacity();
+        while arr.remove(0).is_some() {}
+        assert_eq!(arr.capacity(), capacity);
+        arr.shrink_to_fit();
+        assert_eq!(arr.capacity(), 0);
+    }
+
+    #[test]
+    fn should_compare_elements_regardless_of_layout() {
+        let (arr, _) = wrapped_ring();
+        let other: ArrayDeque<_> = arr.iter().copied().collect();
+        assert_ne!(arr.offset, other.offset);
+        test_utils::assert_compare_by_elements(arr, other);
+    }
+
+    #[test]
+    fn should_index_elements() {
+        let mut arr: ArrayDeque<_> = (0..10).collect();
+        arr[3] = 30;
+        assert_eq!(arr[3], 30);
+        assert_eq!(arr[9], 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_when_indexing_out_of_bounds() {
+        let arr: ArrayDeque<_> = (0..10).collect();
+        let _ = arr[10];
+    }
+
     #[test]
     fn should_drop_elements() {
         struct DropWatcher<'a>(&'a mut bool);
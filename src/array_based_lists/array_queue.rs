@@ -2,13 +2,24 @@
 //!
 //! See Chapter 2.3
 
+use crate::array_based_lists::raw_buf::RawBuf;
+use crate::array_based_lists::resize_policy::ResizePolicy;
+use crate::array_based_lists::{range_of, ring};
+use crate::error::{InsertError, OdsError};
 use crate::queue::Queue;
-use std::mem::MaybeUninit;
-use std::ptr;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::mem::{self, MaybeUninit};
+use core::ops::RangeBounds;
+use core::ptr;
+
+pub use crate::array_based_lists::ring::{Drain, IntoIter, Iter, IterMut};
 
 /// ArrayQueue: An Array-Based Queue
 pub struct ArrayQueue<T> {
-    buf: Box<[MaybeUninit<T>]>,
+    buf: RawBuf<T>,
     len: usize,
     offset: usize,
 }
@@ -16,7 +27,16 @@ pub struct ArrayQueue<T> {
 impl<T> ArrayQueue<T> {
     pub fn new() -> Self {
         ArrayQueue {
-            buf: [].into(),
+            buf: RawBuf::new(),
+            len: 0,
+            offset: 0,
+        }
+    }
+
+    /// Create a new ArrayQueue able to hold `capacity` elements without reallocating
+    pub fn with_capacity(capacity: usize) -> Self {
+        ArrayQueue {
+            buf: RawBuf::with_capacity(ring::capacity(capacity)),
             len: 0,
             offset: 0,
         }
@@ -24,14 +44,12 @@ impl<T> ArrayQueue<T> {
 
     pub fn add(&mut self, element: T) {
         let len = self.len;
-        if len + 1 > self.buf.len() {
-            self.resize();
+        if len == self.buf.capacity() {
+            self.resize(self.buf.policy().grown_capacity(len));
         }
+        let position = ring::mask(len + self.offset, self.buf.len());
         unsafe {
-            *self
-                .buf
-                .get_unchecked_mut((len + self.offset) % self.buf.len()) =
-                MaybeUninit::new(element);
+            *self.buf.get_unchecked_mut(position) = MaybeUninit::new(element);
         }
         self.len += 1;
     }
@@ -44,45 +62,280 @@ impl<T> ArrayQueue<T> {
                 let p = self.buf.as_mut_ptr().add(self.offset);
                 element = p.read().assume_init();
             }
-            self.offset = (self.offset + 1) % self.buf.len();
+            self.offset = ring::mask(self.offset + 1, self.buf.len());
             self.len -= 1;
-            if self.buf.len() >= 3 * self.len {
-                self.resize();
-            }
+            self.shrink();
             Some(element)
         } else {
             None
         }
     }
 
-    fn resize(&mut self) {
-        let len = self.len;
-        let offset = self.offset;
-        let old_capacity = self.buf.len();
-        let new_capacity = usize::max(1, 2 * len);
-        let mut new_buffer = Vec::with_capacity(new_capacity);
-        new_buffer.resize_with(new_capacity, MaybeUninit::uninit);
-        let mut new_buffer = new_buffer.into_boxed_slice();
+    /// Insert given element at the end of the queue
+    ///
+    /// Hands the element back if the backing array cannot grow.
+    pub fn try_add(&mut self, element: T) -> Result<(), InsertError<T>> {
+        if let Err(error) = self.try_reserve(1) {
+            return Err(InsertError::new(error, element));
+        }
+        self.add(element);
+        Ok(())
+    }
+
+    /// Remove and return the element in front of the queue
+    ///
+    /// Fails if the queue is empty
+    pub fn try_remove(&mut self) -> Result<T, OdsError> {
+        self.remove().ok_or(OdsError::Empty)
+    }
+
+    /// Iterate over references to the elements, from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(unsafe { ring::as_slices(&self.buf, self.offset, self.len) })
+    }
+
+    /// Iterate over mutable references to the elements, from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(unsafe { ring::as_mut_slices(&mut self.buf, self.offset, self.len) })
+    }
+
+    /// Rotate the queue so that the element at index `i` moves to index `(i + r) % len`
+    ///
+    /// Moves `min(r, len - r)` elements, and none if the queue is full.
+    pub fn rotate(&mut self, r: usize) {
+        self.offset = ring::rotate(&mut self.buf, self.offset, self.len, r);
+    }
+
+    /// The two contiguous segments holding the elements, from front to back
+    ///
+    /// The second one is empty if the elements do not wrap around the end of the buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        unsafe { ring::as_slices(&self.buf, self.offset, self.len) }
+    }
+
+    /// The two contiguous segments holding the elements, from front to back
+    ///
+    /// The second one is empty if the elements do not wrap around the end of the buffer.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        unsafe { ring::as_mut_slices(&mut self.buf, self.offset, self.len) }
+    }
+
+    /// Move the elements in a single segment of the buffer, without reallocating,
+    /// and return it
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.offset = ring::make_contiguous(&mut self.buf, self.offset, self.len);
+        unsafe { ring::as_mut_slices(&mut self.buf, self.offset, self.len).0 }
+    }
+
+    /// Drop all the elements, keeping the allocated capacity
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keep the first `len` elements and drop the others, keeping the allocated capacity
+    ///
+    /// Has no effect if `len` is greater than the queue length.
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len;
+        if len >= old_len {
+            return;
+        }
+        self.len = len;
         unsafe {
-            if offset + len > old_capacity {
-                // Copy offset..old_capacity
-                let src = self.buf.as_ptr().add(self.offset);
-                let dst = new_buffer.as_mut_ptr();
-                ptr::copy_nonoverlapping(src, dst, old_capacity - offset);
-                // Copy 0..(offset + len - old_capacity)
-                let src = self.buf.as_ptr();
-                let dst = new_buffer.as_mut_ptr().add(old_capacity - offset);
-                ptr::copy_nonoverlapping(src, dst, offset + len - old_capacity);
-            } else {
-                // Copy offset..offset + len
-                let src = self.buf.as_ptr().add(self.offset);
-                let dst = new_buffer.as_mut_ptr();
-                ptr::copy_nonoverlapping(src, dst, len);
-            }
+            let start = ring::mask(self.offset + len, self.buf.len());
+            let (front, back) = ring::as_mut_slices(&mut self.buf, start, old_len - len);
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
         }
-        self.buf = new_buffer;
+    }
+
+    /// Remove the elements in given range, yielding them in order
+    ///
+    /// The allocated capacity is kept.
+    /// The elements that are not consumed are dropped with the iterator.
+    ///
+    /// # Panics
+    /// Panics if the range is decreasing or ends after `len`
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let range = range_of(range, self.len);
+        Drain::new(&mut self.buf, &mut self.offset, &mut self.len, range)
+    }
+
+    /// The number of elements the queue can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Make room for at least `additional` more elements
+    ///
+    /// # Panics
+    /// Panics if the new capacity overflows `usize`
+    pub fn reserve(&mut self, additional: usize) {
+        let len = self.len;
+        let required = len.checked_add(additional).expect("capacity overflow");
+        if required > self.buf.capacity() {
+            let grown = self.buf.policy().grown_capacity(len);
+            self.resize(usize::max(required, grown));
+        }
+    }
+
+    /// Make room for at least `additional` more elements
+    ///
+    /// Fails instead of panicking or aborting if the new buffer cannot be allocated.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), OdsError> {
+        let len = self.len;
+        let required = len
+            .checked_add(additional)
+            .ok_or_else(OdsError::capacity_overflow::<T>)?;
+        if required > self.buf.capacity() {
+            let grown = self.buf.policy().try_grown_capacity(len).unwrap_or(0);
+            self.try_resize(usize::max(required, grown))?;
+        }
+        Ok(())
+    }
+
+    /// Release the unused capacity
+    pub fn shrink_to_fit(&mut self) {
+        if self.buf.capacity() > self.len {
+            self.resize(self.len);
+        }
+    }
+
+    /// The policy used to grow and shrink the backing array
+    pub fn resize_policy(&self) -> ResizePolicy {
+        self.buf.policy()
+    }
+
+    /// Change the policy used to grow and shrink the backing array
+    ///
+    /// Takes effect from the next resize.
+    pub fn set_resize_policy(&mut self, policy: ResizePolicy) {
+        self.buf.set_policy(policy);
+    }
+
+    /// Move the elements to a new buffer able to hold `capacity` elements, rounded up to a power of two
+    #[cold]
+    #[inline(never)]
+    fn resize(&mut self, capacity: usize) {
+        let capacity = ring::capacity(capacity);
+        if capacity == self.buf.capacity() {
+            return;
+        }
+        unsafe { self.buf.reallocate(self.offset, self.len, capacity) };
         self.offset = 0;
     }
+
+    /// Move the elements to a new buffer able to hold `capacity` elements, rounded up to a power of two,
+    /// if it can be allocated
+    #[cold]
+    #[inline(never)]
+    fn try_resize(&mut self, capacity: usize) -> Result<(), OdsError> {
+        let capacity = ring::try_capacity(capacity).ok_or_else(OdsError::capacity_overflow::<T>)?;
+        if capacity == self.buf.capacity() {
+            return Ok(());
+        }
+        unsafe { self.buf.try_reallocate(self.offset, self.len, capacity)? };
+        self.offset = 0;
+        Ok(())
+    }
+
+    /// Shrink the backing buffer if it is too sparse
+    ///
+    /// Shrinking is best effort: the buffer is kept if a smaller one cannot be allocated.
+    fn shrink(&mut self) {
+        let capacity = self.buf.capacity();
+        if let Some(capacity) = self.buf.policy().shrunk_capacity(capacity, self.len) {
+            let _ = self.try_resize(capacity);
+        }
+    }
+}
+
+impl<T> IntoIterator for ArrayQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        let buf = mem::replace(&mut self.buf, RawBuf::new());
+        let len = mem::replace(&mut self.len, 0);
+        let offset = mem::replace(&mut self.offset, 0);
+        IntoIter::new(buf, offset, len)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayQueue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for ArrayQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut arr = Self::new();
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T> Extend<T> for ArrayQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.add(element);
+        }
+    }
+}
+
+impl<T: Clone> Clone for ArrayQueue<T> {
+    fn clone(&self) -> Self {
+        let mut arr = Self::with_capacity(self.len);
+        arr.set_resize_policy(self.resize_policy());
+        arr.extend(self.iter().cloned());
+        arr
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArrayQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArrayQueue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ArrayQueue<T> {}
+
+impl<T: PartialOrd> PartialOrd for ArrayQueue<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for ArrayQueue<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for ArrayQueue<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.iter().for_each(|element| element.hash(state));
+    }
 }
 
 impl<T> Default for ArrayQueue<T> {
@@ -93,11 +346,7 @@ impl<T> Default for ArrayQueue<T> {
 
 impl<T> Drop for ArrayQueue<T> {
     fn drop(&mut self) {
-        loop {
-            if self.remove().is_none() {
-                break;
-            }
-        }
+        self.clear();
     }
 }
 
@@ -111,11 +360,242 @@ impl<T> Queue for ArrayQueue<T> {
     fn remove(&mut self) -> Option<Self::Item> {
         ArrayQueue::remove(self)
     }
+
+    fn peek(&self) -> Option<&Self::Item> {
+        self.iter().next()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// RingBuffer: a bounded queue evicting its oldest element when full
+///
+/// The buffer is allocated once, with exactly the requested capacity, and never reallocated.
+pub struct RingBuffer<T> {
+    buf: RawBuf<T>,
+    len: usize,
+    offset: usize,
+}
+
+impl<T> RingBuffer<T> {
+    /// Create a new RingBuffer holding at most `capacity` elements
+    pub fn with_capacity(capacity: usize) -> Self {
+        RingBuffer {
+            buf: RawBuf::with_capacity(capacity),
+            len: 0,
+            offset: 0,
+        }
+    }
+
+    /// Create a new RingBuffer holding at most `capacity` elements
+    ///
+    /// Fails instead of panicking or aborting if the buffer cannot be allocated.
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, OdsError> {
+        Ok(RingBuffer {
+            buf: RawBuf::try_with_capacity(capacity)?,
+            len: 0,
+            offset: 0,
+        })
+    }
+
+    /// The ring buffer length
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the ring buffer empty ?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Will the next `add` evict the oldest element ?
+    pub fn is_full(&self) -> bool {
+        self.len == self.buf.capacity()
+    }
+
+    /// The maximum number of elements held by the ring buffer
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Insert given element as the newest one, evicting and returning the oldest one when full
+    ///
+    /// A ring buffer of capacity 0 returns `element` right away.
+    pub fn add(&mut self, element: T) -> Option<T> {
+        let capacity = self.buf.capacity();
+        if capacity == 0 {
+            return Some(element);
+        }
+        let position = ring::wrap(self.offset + self.len, capacity);
+        let slot = mem::replace(&mut self.buf[position], MaybeUninit::new(element));
+        if self.len == capacity {
+            self.offset = ring::wrap(self.offset + 1, capacity);
+            Some(unsafe { slot.assume_init() })
+        } else {
+            self.len += 1;
+            None
+        }
+    }
+
+    /// Remove and return the oldest element
+    pub fn remove(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let element = unsafe { self.buf[self.offset].as_ptr().read() };
+        self.offset = ring::wrap(self.offset + 1, self.buf.capacity());
+        self.len -= 1;
+        Some(element)
+    }
+
+    /// Get a reference to the element at given index, counting from the oldest one
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            let position = ring::wrap(self.offset + index, self.buf.capacity());
+            Some(unsafe { &*self.buf[position].as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the element at given index, counting from the oldest one
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            let position = ring::wrap(self.offset + index, self.buf.capacity());
+            Some(unsafe { &mut *self.buf[position].as_mut_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Get a reference to the element at given index, counting from the newest one
+    pub fn get_newest(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            self.get(self.len - index - 1)
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the element at given index, counting from the newest one
+    pub fn get_newest_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            self.get_mut(self.len - index - 1)
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over references to the elements, from oldest to newest
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(unsafe { ring::as_slices(&self.buf, self.offset, self.len) })
+    }
+
+    /// Iterate over mutable references to the elements, from oldest to newest
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(unsafe { ring::as_mut_slices(&mut self.buf, self.offset, self.len) })
+    }
+
+    /// Drop all the elements
+    pub fn clear(&mut self) {
+        let len = mem::replace(&mut self.len, 0);
+        unsafe {
+            let (front, back) = ring::as_mut_slices(&mut self.buf, self.offset, len);
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+impl<T> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        let buf = mem::replace(&mut self.buf, RawBuf::new());
+        let len = mem::replace(&mut self.len, 0);
+        let offset = mem::replace(&mut self.offset, 0);
+        IntoIter::new(buf, offset, len)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RingBuffer<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T> {
+    /// Insert the elements in order, dropping the evicted ones
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.add(element);
+        }
+    }
+}
+
+impl<T: Clone> Clone for RingBuffer<T> {
+    fn clone(&self) -> Self {
+        let mut arr = Self::with_capacity(self.capacity());
+        arr.extend(self.iter().cloned());
+        arr
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RingBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Queue for RingBuffer<T> {
+    type Item = T;
+
+    /// Insert given element, dropping the oldest one when full
+    fn add(&mut self, item: Self::Item) {
+        RingBuffer::add(self, item);
+    }
+
+    fn remove(&mut self) -> Option<Self::Item> {
+        RingBuffer::remove(self)
+    }
+
+    fn peek(&self) -> Option<&Self::Item> {
+        self.get(0)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ArrayQueue;
+    use super::{ArrayQueue, RingBuffer};
+    use crate::array_based_lists::resize_policy::ResizePolicy;
+    use crate::array_based_lists::test_utils;
+    use crate::error::OdsError;
+    use crate::queue::Queue;
     use std::collections::VecDeque;
 
     #[test]
@@ -154,6 +634,195 @@ mod tests {
         assert_eq!(deque.pop_front(), None);
     }
 
+    #[test]
+    fn should_iterate_over_elements() {
+        let (mut arr, mut deque) = wrapped_ring();
+        assert!(arr.iter().eq(deque.iter()));
+        for element in &mut arr {
+            *element *= 2;
+        }
+        for element in deque.iter_mut() {
+            *element *= 2;
+        }
+        assert!((&arr).into_iter().eq(deque.iter()));
+        assert!(arr.into_iter().eq(deque));
+    }
+
+    #[test]
+    fn should_collect_and_iterate_from_both_ends() {
+        let arr: ArrayQueue<_> = (0..10).collect();
+        let mut iter = arr.into_iter();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(9));
+        assert_eq!(iter.len(), 8);
+        assert!(iter.eq(1..9));
+    }
+
+    /// Build a ring wrapping around the end of its buffer, along with its reference
+    fn wrapped_ring() -> (ArrayQueue<i32>, VecDeque<i32>) {
+        let (arr, deque) = test_utils::wrapped_ring::<ArrayQueue<_>>();
+        assert!(arr.offset + arr.len > arr.buf.len());
+        (arr, deque)
+    }
+
+    #[test]
+    fn should_rotate_elements() {
+        for r in 0..30 {
+            let (mut arr, mut deque) = wrapped_ring();
+            arr.rotate(r);
+            deque.rotate_right(r % deque.len());
+            assert!(arr.iter().eq(deque.iter()));
+        }
+        let mut arr: ArrayQueue<_> = (0..16).collect();
+        arr.rotate(3);
+        assert_eq!(arr.capacity(), 16);
+        assert!(arr.iter().copied().eq((13..16).chain(0..13)));
+    }
+
+    #[test]
+    fn should_expose_slices() {
+        let (mut arr, deque) = wrapped_ring();
+        let (front, back) = arr.as_slices();
+        assert_eq!((front.len(), back.len()), (11, 4));
+        assert!(front.iter().chain(back).eq(deque.iter()));
+        let (front, back) = arr.as_mut_slices();
+        front[0] = 100;
+        back[0] = 200;
+        assert_eq!(arr.iter().next(), Some(&100));
+        assert_eq!(arr.iter().nth(11), Some(&200));
+    }
+
+    #[test]
+    fn should_make_elements_contiguous() {
+        let (mut arr, mut deque) = wrapped_ring();
+        let capacity = arr.capacity();
+        arr.make_contiguous().reverse();
+        deque.make_contiguous().reverse();
+        assert!(arr.iter().eq(deque.iter()));
+        assert!(arr.as_slices().1.is_empty());
+        assert_eq!(arr.capacity(), capacity);
+
+        arr.make_contiguous().sort_unstable();
+        assert_eq!(arr.make_contiguous().binary_search(&13), Ok(8));
+        assert!(arr.iter().copied().eq(5..20));
+    }
+
+    #[test]
+    fn should_clear_and_truncate_elements() {
+        let (mut arr, mut deque) = wrapped_ring();
+        let capacity = arr.buf.len();
+        arr.truncate(20);
+        assert_eq!(arr.len, deque.len());
+        arr.truncate(11);
+        deque.truncate(11);
+        assert!(arr.iter().eq(deque.iter()));
+        arr.truncate(4);
+        deque.truncate(4);
+        assert!(arr.iter().eq(deque.iter()));
+        arr.clear();
+        assert_eq!(arr.len, 0);
+        assert_eq!(arr.buf.len(), capacity);
+    }
+
+    #[test]
+    fn should_drain_elements() {
+        for start in 0..15 {
+            for end in start..15 {
+                let (mut arr, mut deque) = wrapped_ring();
+                assert!(arr.drain(start..end).eq(deque.drain(start..end)));
+                assert!(arr.iter().eq(deque.iter()));
+                // The ring is still usable
+                arr.extend(20..30);
+                deque.extend(20..30);
+                assert!(arr.iter().eq(deque.iter()));
+            }
+        }
+        let (mut arr, mut deque) = wrapped_ring();
+        let mut drain = arr.drain(2..=9);
+        assert_eq!(drain.len(), 8);
+        assert_eq!(drain.next_back(), deque.get(9).copied());
+        assert_eq!(drain.next(), deque.get(2).copied());
+        drop(drain);
+        deque.drain(2..=9);
+        assert!(arr.iter().eq(deque.iter()));
+        assert!(arr.drain(..).eq(deque.drain(..)));
+        assert_eq!(arr.len, 0);
+    }
+
+    #[test]
+    fn should_manage_capacity() {
+        // Capacities are rounded up to powers of two
+        let mut arr = ArrayQueue::with_capacity(10);
+        assert_eq!(arr.capacity(), 16);
+        for i in 0..16 {
+            arr.add(i);
+        }
+        assert_eq!(arr.capacity(), 16);
+        arr.reserve(5);
+        assert_eq!(arr.capacity(), 32);
+        arr.reserve(100);
+        assert_eq!(arr.capacity(), 128);
+        arr.shrink_to_fit();
+        assert_eq!(arr.capacity(), 16);
+        assert!(arr.iter().copied().eq(0..16));
+        arr.truncate(10);
+        assert!(arr.iter().copied().eq(0..10));
+    }
+
+    #[test]
+    fn should_try_to_add_and_remove_elements() {
+        let mut arr = ArrayQueue::new();
+        arr.try_reserve(10).unwrap();
+        assert_eq!(arr.capacity(), 16);
+        for i in 0..16 {
+            arr.try_add(i).unwrap();
+        }
+        assert_eq!(arr.capacity(), 16);
+        assert_eq!(arr.try_remove(), Ok(0));
+        assert_eq!(
+            arr.try_reserve(usize::MAX),
+            Err(OdsError::capacity_overflow::<i32>())
+        );
+        let mut arr = ArrayQueue::<u8>::new();
+        assert_eq!(arr.try_remove(), Err(OdsError::Empty));
+        assert_eq!(
+            arr.try_reserve(1 << (usize::BITS - 2)),
+            Err(OdsError::AllocationFailed)
+        );
+    }
+
+    #[test]
+    fn should_follow_resize_policy() {
+        let mut arr = ArrayQueue::new();
+        arr.set_resize_policy(ResizePolicy::new(4, Some(8)));
+        for i in 0..5 {
+            arr.add(i);
+        }
+        // 1 -> 4 -> 16
+        assert_eq!(arr.capacity(), 16);
+        arr.remove();
+        arr.remove();
+        arr.remove();
+        assert_eq!(arr.capacity(), 8);
+
+        arr.set_resize_policy(ResizePolicy::default().without_shrinking());
+        arr.reserve(100);
+        let capacity = arr.capacity();
+        while arr.remove().is_some() {}
+        assert_eq!(arr.capacity(), capacity);
+        arr.shrink_to_fit();
+        assert_eq!(arr.capacity(), 0);
+    }
+
+    #[test]
+    fn should_compare_elements_regardless_of_layout() {
+        let (arr, _) = wrapped_ring();
+        let other: ArrayQueue<_> = arr.iter().copied().collect();
+        assert_ne!(arr.offset, other.offset);
+        test_utils::assert_compare_by_elements(arr, other);
+    }
+
     #[test]
     fn should_drop_elements() {
         struct DropWatcher<'a>(&'a mut bool);
@@ -169,4 +838,92 @@ mod tests {
         }
         assert!(dropped);
     }
+
+    #[test]
+    fn should_evict_oldest_elements() {
+        let mut ring = RingBuffer::with_capacity(3);
+        assert_eq!(ring.add(0), None);
+        assert_eq!(ring.add(1), None);
+        assert!(!ring.is_full());
+        assert_eq!(ring.add(2), None);
+        assert!(ring.is_full());
+        assert_eq!(ring.add(3), Some(0));
+        assert_eq!(ring.add(4), Some(1));
+        assert!(ring.iter().copied().eq(2..5));
+        assert_eq!(ring.remove(), Some(2));
+        assert_eq!(ring.add(5), None);
+        assert_eq!(ring.add(6), Some(3));
+        assert!(ring.iter().copied().eq(4..7));
+        assert!(ring.into_iter().rev().eq((4..7).rev()));
+    }
+
+    #[test]
+    fn should_index_ring_buffer_from_both_ends() {
+        let mut ring = RingBuffer::with_capacity(5);
+        ring.extend(0..12);
+        assert_eq!(ring.len(), 5);
+        for i in 0..5 {
+            assert_eq!(ring.get(i), Some(&(7 + i)));
+            assert_eq!(ring.get_newest(i), Some(&(11 - i)));
+        }
+        assert_eq!(ring.get(5), None);
+        assert_eq!(ring.get_newest(5), None);
+        *ring.get_newest_mut(0).unwrap() = 42;
+        *ring.get_mut(0).unwrap() = 24;
+        assert!(ring.iter().copied().eq([24, 8, 9, 10, 42]));
+    }
+
+    #[test]
+    fn should_never_reallocate_ring_buffer() {
+        let mut ring = RingBuffer::with_capacity(43);
+        let slots = ring.buf.as_ptr();
+        for i in 0..1000 {
+            ring.add(i);
+            if i % 3 == 0 {
+                ring.remove();
+            }
+            assert_eq!(ring.capacity(), 43);
+            assert_eq!(ring.buf.as_ptr(), slots);
+        }
+        assert!(ring.iter().copied().eq(958..1000));
+    }
+
+    #[test]
+    fn should_try_to_allocate_ring_buffer() {
+        let mut ring = RingBuffer::try_with_capacity(3).unwrap();
+        ring.extend(0..5);
+        assert!(ring.iter().copied().eq(2..5));
+        assert_eq!(
+            RingBuffer::<u64>::try_with_capacity(usize::MAX).err(),
+            Some(OdsError::capacity_overflow::<u64>())
+        );
+        assert_eq!(
+            RingBuffer::<u8>::try_with_capacity(1 << (usize::BITS - 2)).err(),
+            Some(OdsError::AllocationFailed)
+        );
+    }
+
+    #[test]
+    fn should_return_elements_right_away_without_capacity() {
+        let mut ring = RingBuffer::with_capacity(0);
+        assert!(ring.is_full());
+        assert_eq!(ring.add("foo"), Some("foo"));
+        assert_eq!(ring.remove(), None);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn should_drop_ring_buffer_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let mut ring = RingBuffer::with_capacity(4);
+        for _ in 0..10 {
+            ring.add(Rc::clone(&watcher));
+        }
+        assert_eq!(Rc::strong_count(&watcher), 5);
+        Queue::add(&mut ring, Rc::clone(&watcher));
+        assert_eq!(Rc::strong_count(&watcher), 5);
+        drop(ring);
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
 }
@@ -2,13 +2,24 @@
 //!
 //! See Chapter 2.1
 
+use crate::array_based_lists::range_of;
+use crate::array_based_lists::raw_buf::RawBuf;
+use crate::array_based_lists::resize_policy::ResizePolicy;
+use crate::error::{InsertError, OdsError};
+use crate::list::List;
 use crate::stack::Stack;
-use std::mem::MaybeUninit;
-use std::ptr;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, FusedIterator};
+use core::mem::{self, MaybeUninit};
+use core::ops::{Index, IndexMut, Range, RangeBounds};
+use core::{ptr, slice};
 
 /// ArrayStack: Fast Stack Operations Using an Array
 pub struct ArrayStack<T> {
-    buf: Box<[MaybeUninit<T>]>,
+    buf: RawBuf<T>,
     len: usize,
 }
 
@@ -16,7 +27,15 @@ impl<T> ArrayStack<T> {
     /// Create a new ArrayStack
     pub fn new() -> Self {
         Self {
-            buf: [].into(),
+            buf: RawBuf::new(),
+            len: 0,
+        }
+    }
+
+    /// Create a new ArrayStack able to hold `capacity` elements without reallocating
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: RawBuf::with_capacity(capacity),
             len: 0,
         }
     }
@@ -67,8 +86,8 @@ impl<T> ArrayStack<T> {
             assert_failed(index, len);
         }
 
-        if len == self.buf.len() {
-            self.resize();
+        if len == self.buf.capacity() {
+            self.resize(self.buf.policy().grown_capacity(len));
         }
 
         unsafe {
@@ -79,6 +98,47 @@ impl<T> ArrayStack<T> {
         self.len += 1;
     }
 
+    /// Insert all the elements of `iter` at given index, shifting the following elements
+    /// to the right only once
+    ///
+    /// The buffer is grown once using the iterator lower size hint. Elements beyond that hint
+    /// are collected first, then inserted with a second shift.
+    ///
+    /// # Panics
+    /// Panics if `index` > `len`
+    pub fn add_all<I: IntoIterator<Item = T>>(&mut self, index: usize, iter: I) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+        let len = self.len;
+        if index > len {
+            assert_failed(index, len);
+        }
+
+        let mut iter = iter.into_iter();
+        let additional = iter.size_hint().0;
+        self.reserve(additional);
+        let rest: Vec<T> = {
+            let mut gap = Gap::open(self, index, additional);
+            for element in iter.by_ref().take(additional) {
+                gap.write(element);
+            }
+            if gap.is_full() {
+                iter.collect()
+            } else {
+                Vec::new()
+            }
+        };
+        if !rest.is_empty() {
+            self.add_all(index + additional, rest);
+        }
+    }
+
     /// Remove at returns element at given index, shifting all following elements to the left
     ///
     /// # Panics
@@ -100,24 +160,537 @@ impl<T> ArrayStack<T> {
             ptr::copy(p.offset(1), p, len - index - 1);
         }
         self.len -= 1;
-        if self.buf.len() >= 3 * self.len {
-            self.resize();
+        self.shrink();
+        element
+    }
+
+    /// Remove and return the element at given index, replacing it with the top element
+    ///
+    /// Does not preserve the order of the elements, but runs in O(1).
+    ///
+    /// # Panics
+    /// Panics if `index` >= `len`
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!(
+                "swap_remove index (is {}) should be < len (is {})",
+                index, len
+            );
+        }
+        let len = self.len;
+        if index >= len {
+            assert_failed(index, len);
+        }
+        let element;
+        unsafe {
+            let p = self.buf.as_mut_ptr();
+            element = p.add(index).read().assume_init();
+            ptr::copy(p.add(len - 1), p.add(index), 1);
         }
+        self.len -= 1;
+        self.shrink();
         element
     }
 
-    fn resize(&mut self) {
+    /// Swap the elements at given indices
+    ///
+    /// # Panics
+    /// Panics if `a` >= `len` or `b` >= `len`
+    pub fn swap(&mut self, a: usize, b: usize) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("index (is {}) should be < len (is {})", index, len);
+        }
+        let len = self.len;
+        if a >= len {
+            assert_failed(a, len);
+        }
+        if b >= len {
+            assert_failed(b, len);
+        }
+        unsafe {
+            let p = self.buf.as_mut_ptr();
+            ptr::swap(p.add(a), p.add(b));
+        }
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// Hands the element back if `index` > `len` or if the backing array cannot grow.
+    pub fn try_add(&mut self, index: usize, element: T) -> Result<(), InsertError<T>> {
+        let len = self.len;
+        if index > len {
+            let error = OdsError::IndexOutOfBounds { index, len };
+            return Err(InsertError::new(error, element));
+        }
+        if let Err(error) = self.try_reserve(1) {
+            return Err(InsertError::new(error, element));
+        }
+        self.add(index, element);
+        Ok(())
+    }
+
+    /// Remove and return the element at given index, shifting all following elements to the left
+    ///
+    /// Fails if `index` >= `len`
+    pub fn try_remove(&mut self, index: usize) -> Result<T, OdsError> {
         let len = self.len;
-        let new_capacity = usize::max(1, 2 * len);
-        let mut new_buffer = Vec::with_capacity(new_capacity);
-        new_buffer.resize_with(new_capacity, MaybeUninit::uninit);
-        let mut new_buffer = new_buffer.into_boxed_slice();
+        if index < len {
+            Ok(self.remove(index))
+        } else {
+            Err(OdsError::IndexOutOfBounds { index, len })
+        }
+    }
+
+    /// Insert given element on top of the stack
+    ///
+    /// Hands the element back if the backing array cannot grow.
+    pub fn try_push(&mut self, element: T) -> Result<(), InsertError<T>> {
+        self.try_add(self.len, element)
+    }
+
+    /// Iterate over references to the elements, from bottom to top
+    pub fn iter(&self) -> Iter<'_, T> {
+        let elements = unsafe { slice::from_raw_parts(self.buf.as_ptr() as *const T, self.len) };
+        Iter {
+            inner: elements.iter(),
+        }
+    }
+
+    /// Iterate over mutable references to the elements, from bottom to top
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let elements =
+            unsafe { slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut T, self.len) };
+        IterMut {
+            inner: elements.iter_mut(),
+        }
+    }
+
+    /// Drop all the elements, keeping the allocated capacity
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keep the first `len` elements and drop the others, keeping the allocated capacity
+    ///
+    /// Has no effect if `len` is greater than the array stack length.
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len;
+        if len >= old_len {
+            return;
+        }
+        self.len = len;
         unsafe {
-            let src = self.buf.as_ptr();
-            let dst = new_buffer.as_mut_ptr();
-            ptr::copy_nonoverlapping(src, dst, len);
+            let p = self.buf.as_mut_ptr().add(len) as *mut T;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(p, old_len - len));
+        }
+    }
+
+    /// Remove the elements in given range, yielding them in order
+    ///
+    /// The allocated capacity is kept.
+    /// The elements that are not consumed are dropped with the iterator.
+    ///
+    /// # Panics
+    /// Panics if the range is decreasing or ends after `len`
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let range = range_of(range, self.len);
+        // Only keep the head while draining, so leaking the iterator leaks the tail
+        let old_len = mem::replace(&mut self.len, range.start);
+        Drain {
+            arr: self,
+            remaining: range.clone(),
+            range,
+            old_len,
+        }
+    }
+
+    /// The number of elements the array stack can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Make room for at least `additional` more elements
+    ///
+    /// # Panics
+    /// Panics if the new capacity overflows `usize`
+    pub fn reserve(&mut self, additional: usize) {
+        let len = self.len;
+        let required = len.checked_add(additional).expect("capacity overflow");
+        if required > self.buf.capacity() {
+            let grown = self.buf.policy().grown_capacity(len);
+            self.resize(usize::max(required, grown));
+        }
+    }
+
+    /// Make room for at least `additional` more elements
+    ///
+    /// Fails instead of panicking or aborting if the new array cannot be allocated.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), OdsError> {
+        let len = self.len;
+        let required = len
+            .checked_add(additional)
+            .ok_or_else(OdsError::capacity_overflow::<T>)?;
+        if required > self.buf.capacity() {
+            let grown = self.buf.policy().try_grown_capacity(len).unwrap_or(0);
+            self.try_resize(usize::max(required, grown))?;
+        }
+        Ok(())
+    }
+
+    /// Release the unused capacity
+    pub fn shrink_to_fit(&mut self) {
+        if self.buf.capacity() > self.len {
+            self.resize(self.len);
+        }
+    }
+
+    /// The policy used to grow and shrink the backing array
+    pub fn resize_policy(&self) -> ResizePolicy {
+        self.buf.policy()
+    }
+
+    /// Change the policy used to grow and shrink the backing array
+    ///
+    /// Takes effect from the next resize.
+    pub fn set_resize_policy(&mut self, policy: ResizePolicy) {
+        self.buf.set_policy(policy);
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        unsafe { self.buf.reallocate(0, self.len, capacity) };
+    }
+
+    /// Move the elements to a new array able to hold `capacity` elements, if it can be allocated
+    fn try_resize(&mut self, capacity: usize) -> Result<(), OdsError> {
+        unsafe { self.buf.try_reallocate(0, self.len, capacity) }
+    }
+
+    /// Shrink the backing array if it is too sparse
+    ///
+    /// Shrinking is best effort: the array is kept if a smaller one cannot be allocated.
+    fn shrink(&mut self) {
+        let capacity = self.buf.capacity();
+        if let Some(capacity) = self.buf.policy().shrunk_capacity(capacity, self.len) {
+            let _ = self.try_resize(capacity);
+        }
+    }
+}
+
+/// An iterator over references to the elements of an `ArrayStack`
+pub struct Iter<'a, T> {
+    inner: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over mutable references to the elements of an `ArrayStack`
+pub struct IterMut<'a, T> {
+    inner: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of an `ArrayStack`
+pub struct IntoIter<T> {
+    buf: RawBuf<T>,
+    start: usize,
+    end: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start < self.end {
+            let element = unsafe { self.buf[self.start].as_ptr().read() };
+            self.start += 1;
+            Some(element)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start < self.end {
+            self.end -= 1;
+            Some(unsafe { self.buf[self.end].as_ptr().read() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        let start = mem::replace(&mut self.start, self.end);
+        unsafe {
+            let p = self.buf.as_mut_ptr().add(start) as *mut T;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(p, self.end - start));
+        }
+    }
+}
+
+/// A draining iterator over a range of the elements of an `ArrayStack`
+///
+/// The remaining elements are dropped and the tail is shifted to close the gap
+/// when the iterator is dropped.
+pub struct Drain<'a, T> {
+    arr: &'a mut ArrayStack<T>,
+    /// The drained range
+    range: Range<usize>,
+    /// The elements of `range` that were not yielded yet
+    remaining: Range<usize>,
+    /// The length of the array stack before draining
+    old_len: usize,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let index = self.remaining.next()?;
+        Some(unsafe { self.arr.buf[index].as_ptr().read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        let index = self.remaining.next_back()?;
+        Some(unsafe { self.arr.buf[index].as_ptr().read() })
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+
+        let tail = self.old_len - self.range.end;
+        unsafe {
+            let p = self.arr.buf.as_mut_ptr();
+            ptr::copy(p.add(self.range.end), p.add(self.range.start), tail);
+        }
+        self.arr.len = self.range.start + tail;
+    }
+}
+
+/// A gap of uninitialized slots opened by `add_all` in front of the tail elements
+///
+/// Dropping it moves the tail right after the written elements, even if the iterator panicked.
+struct Gap<'a, T> {
+    arr: &'a mut ArrayStack<T>,
+    index: usize,
+    end: usize,
+    tail: usize,
+}
+
+impl<'a, T> Gap<'a, T> {
+    /// Move the elements from `index` `len` slots to the right
+    fn open(arr: &'a mut ArrayStack<T>, index: usize, len: usize) -> Self {
+        debug_assert!(arr.len + len <= arr.buf.capacity());
+        let tail = arr.len - index;
+        unsafe {
+            let p = arr.buf.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(len), tail);
+        }
+        arr.len = index;
+        Gap {
+            arr,
+            index,
+            end: index + len,
+            tail,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.index == self.end
+    }
+
+    fn write(&mut self, element: T) {
+        debug_assert!(!self.is_full());
+        self.arr.buf[self.index] = MaybeUninit::new(element);
+        self.index += 1;
+    }
+}
+
+impl<T> Drop for Gap<'_, T> {
+    fn drop(&mut self) {
+        unsafe {
+            let p = self.arr.buf.as_mut_ptr();
+            ptr::copy(p.add(self.end), p.add(self.index), self.tail);
+        }
+        self.arr.len = self.index + self.tail;
+    }
+}
+
+impl<T> IntoIterator for ArrayStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        let buf = mem::replace(&mut self.buf, RawBuf::new());
+        let end = mem::replace(&mut self.len, 0);
+        IntoIter { buf, start: 0, end }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayStack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for ArrayStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut arr = Self::new();
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T> Extend<T> for ArrayStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: Clone> Clone for ArrayStack<T> {
+    fn clone(&self) -> Self {
+        let mut arr = Self::with_capacity(self.len);
+        arr.set_resize_policy(self.resize_policy());
+        arr.extend(self.iter().cloned());
+        arr
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArrayStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArrayStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ArrayStack<T> {}
+
+impl<T: PartialOrd> PartialOrd for ArrayStack<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for ArrayStack<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for ArrayStack<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.iter().for_each(|element| element.hash(state));
+    }
+}
+
+impl<T> Index<usize> for ArrayStack<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len;
+        match self.get(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for ArrayStack<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
         }
-        self.buf = new_buffer;
     }
 }
 
@@ -129,10 +702,7 @@ impl<T> Default for ArrayStack<T> {
 
 impl<T> Drop for ArrayStack<T> {
     fn drop(&mut self) {
-        for index in (0..self.len).rev() {
-            // TODO: Prevent reallocation on drop.
-            self.remove(index);
-        }
+        self.clear();
     }
 }
 
@@ -151,11 +721,52 @@ impl<T> Stack for ArrayStack<T> {
             None
         }
     }
+
+    fn peek(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T> List for ArrayStack<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        ArrayStack::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        ArrayStack::get_mut(self, index)
+    }
+
+    fn add(&mut self, index: usize, item: T) {
+        ArrayStack::add(self, index, item)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index < self.len {
+            Some(ArrayStack::remove(self, index))
+        } else {
+            None
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::ArrayStack;
+    use crate::array_based_lists::resize_policy::ResizePolicy;
+    use crate::array_based_lists::test_utils;
+    use crate::error::OdsError;
+    use crate::stack::Stack;
+    use std::vec::Vec;
 
     #[test]
     fn should_add_elements() {
@@ -209,6 +820,306 @@ mod tests {
         assert_eq!(arr.get(0), None);
     }
 
+    #[test]
+    fn should_swap_remove_elements() {
+        let mut arr: ArrayStack<_> = (0..5).collect();
+        assert_eq!(arr.swap_remove(1), 1);
+        assert!(arr.iter().copied().eq([0, 4, 2, 3]));
+        assert_eq!(arr.swap_remove(3), 3);
+        assert!(arr.iter().copied().eq([0, 4, 2]));
+    }
+
+    #[test]
+    fn should_swap_elements() {
+        let mut arr: ArrayStack<_> = (0..5).collect();
+        arr.swap(1, 3);
+        arr.swap(2, 2);
+        assert!(arr.iter().copied().eq([0, 3, 2, 1, 4]));
+    }
+
+    #[test]
+    #[should_panic(expected = "index (is 5) should be < len (is 5)")]
+    fn should_panic_when_swapping_out_of_bounds() {
+        let mut arr: ArrayStack<_> = (0..5).collect();
+        arr.swap(0, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "swap_remove index (is 3) should be < len (is 3)")]
+    fn should_panic_when_swap_removing_out_of_bounds() {
+        let mut arr: ArrayStack<_> = (0..3).collect();
+        arr.swap_remove(3);
+    }
+
+    #[test]
+    fn should_iterate_over_elements() {
+        let mut arr: ArrayStack<_> = (0..10).collect();
+        arr.extend(10..15);
+        assert!(arr.iter().copied().eq(0..15));
+        assert!(arr.iter().rev().copied().eq((0..15).rev()));
+        assert_eq!(arr.iter().len(), 15);
+        for element in &mut arr {
+            *element *= 2;
+        }
+        assert!(arr
+            .iter_mut()
+            .map(|element| *element)
+            .eq((0..15).map(|i| i * 2)));
+        let mut iter = arr.into_iter();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(28));
+        assert_eq!(iter.len(), 13);
+        assert!(iter.eq((1..14).map(|i| i * 2)));
+    }
+
+    #[test]
+    fn should_drop_remaining_elements_of_into_iter() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let arr: ArrayStack<_> = (0..10).map(|_| Rc::clone(&watcher)).collect();
+        let mut iter = arr.into_iter();
+        iter.next();
+        iter.next_back();
+        assert_eq!(Rc::strong_count(&watcher), 9);
+        drop(iter);
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+
+    #[test]
+    fn should_clear_and_truncate_elements() {
+        let mut arr: ArrayStack<_> = (0..10).collect();
+        let capacity = arr.buf.len();
+        arr.truncate(20);
+        assert_eq!(arr.len(), 10);
+        arr.truncate(4);
+        assert!(arr.iter().copied().eq(0..4));
+        arr.clear();
+        assert!(arr.is_empty());
+        assert_eq!(arr.buf.len(), capacity);
+    }
+
+    #[test]
+    fn should_drain_elements() {
+        let mut arr: ArrayStack<_> = (0..10).collect();
+        let mut vec: Vec<_> = (0..10).collect();
+        assert!(arr.drain(2..5).eq(vec.drain(2..5)));
+        assert!(arr.iter().eq(vec.iter()));
+        let mut drain = arr.drain(1..=3);
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next_back(), Some(6));
+        drop(drain);
+        vec.drain(1..=3);
+        assert!(arr.iter().eq(vec.iter()));
+        assert!(arr.drain(..).eq(vec.drain(..)));
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn should_drop_drained_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let mut arr: ArrayStack<_> = (0..10).map(|_| Rc::clone(&watcher)).collect();
+        let mut drain = arr.drain(3..8);
+        drain.next();
+        assert_eq!(Rc::strong_count(&watcher), 10);
+        drop(drain);
+        assert_eq!(Rc::strong_count(&watcher), 6);
+        assert_eq!(arr.len(), 5);
+        arr.truncate(2);
+        assert_eq!(Rc::strong_count(&watcher), 3);
+    }
+
+    struct LyingIter<I> {
+        inner: I,
+        hint: usize,
+    }
+
+    impl<I: Iterator> Iterator for LyingIter<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<I::Item> {
+            self.inner.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.hint, None)
+        }
+    }
+
+    #[test]
+    fn should_add_all_elements() {
+        for index in 0..=10 {
+            for additional in 0..20 {
+                let mut arr: ArrayStack<_> = (0..10).collect();
+                let mut vec: Vec<_> = (0..10).collect();
+                arr.add_all(index, 100..100 + additional);
+                vec.splice(index..index, 100..100 + additional);
+                assert!(arr.iter().eq(vec.iter()));
+            }
+        }
+    }
+
+    #[test]
+    fn should_resize_once_when_adding_all_elements() {
+        let mut arr: ArrayStack<_> = (0..16).collect();
+        arr.add_all(8, 100..150);
+        assert_eq!(arr.capacity(), 66);
+        assert!(arr.iter().copied().eq((0..8).chain(100..150).chain(8..16)));
+    }
+
+    #[test]
+    fn should_add_all_elements_of_lying_iterators() {
+        for hint in [0, 3, 5, 8, 100] {
+            let mut arr: ArrayStack<_> = (0..10).collect();
+            let iter = LyingIter {
+                inner: 100..105,
+                hint,
+            };
+            arr.add_all(3, iter);
+            assert!(arr.iter().copied().eq((0..3).chain(100..105).chain(3..10)));
+        }
+    }
+
+    #[test]
+    fn should_keep_elements_when_adding_all_panics() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        for index in [2, 8] {
+            let mut arr: ArrayStack<_> = (0..10).map(|_| Rc::clone(&watcher)).collect();
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                arr.add_all(
+                    index,
+                    (0..5).map(|i| {
+                        assert!(i < 3, "iterator panicked");
+                        Rc::clone(&watcher)
+                    }),
+                )
+            }));
+            assert!(result.is_err());
+            assert_eq!(arr.len(), 13);
+            assert_eq!(Rc::strong_count(&watcher), 14);
+            drop(arr);
+            assert_eq!(Rc::strong_count(&watcher), 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 11) should be <= len (is 10)")]
+    fn should_panic_when_adding_all_out_of_bounds() {
+        let mut arr: ArrayStack<_> = (0..10).collect();
+        arr.add_all(11, 0..3);
+    }
+
+    #[test]
+    fn should_manage_capacity() {
+        let mut arr = ArrayStack::with_capacity(10);
+        assert_eq!(arr.capacity(), 10);
+        for i in 0..10 {
+            arr.push(i);
+        }
+        assert_eq!(arr.capacity(), 10);
+        arr.reserve(5);
+        assert!(arr.capacity() >= 15);
+        arr.reserve(100);
+        assert!(arr.capacity() >= 110);
+        arr.shrink_to_fit();
+        assert_eq!(arr.capacity(), 10);
+        assert!(arr.iter().copied().eq(0..10));
+    }
+
+    #[test]
+    fn should_try_to_add_and_remove_elements() {
+        let mut arr = ArrayStack::new();
+        for i in 0..4 {
+            arr.try_push(i).unwrap();
+        }
+        arr.try_add(1, 10).unwrap();
+        assert!(arr.iter().copied().eq([0, 10, 1, 2, 3]));
+        let error = arr.try_add(6, 20).unwrap_err();
+        assert_eq!(
+            error.error(),
+            OdsError::IndexOutOfBounds { index: 6, len: 5 }
+        );
+        assert_eq!(error.into_element(), 20);
+        assert_eq!(arr.try_remove(1), Ok(10));
+        assert_eq!(
+            arr.try_remove(4),
+            Err(OdsError::IndexOutOfBounds { index: 4, len: 4 })
+        );
+        assert!(arr.iter().copied().eq(0..4));
+    }
+
+    #[test]
+    fn should_report_failed_reservations() {
+        let mut arr = ArrayStack::<u64>::new();
+        arr.push(0);
+        assert_eq!(arr.try_reserve(100), Ok(()));
+        assert!(arr.capacity() >= 101);
+        assert_eq!(
+            arr.try_reserve(usize::MAX),
+            Err(OdsError::capacity_overflow::<u64>())
+        );
+        assert_eq!(
+            arr.try_reserve(isize::MAX as usize / 8 + 1),
+            Err(OdsError::capacity_overflow::<u64>())
+        );
+        let mut arr = ArrayStack::<u8>::new();
+        assert_eq!(
+            arr.try_reserve(isize::MAX as usize),
+            Err(OdsError::AllocationFailed)
+        );
+        assert_eq!(arr.try_push(0), Ok(()));
+        assert_eq!(arr.pop(), Some(0));
+    }
+
+    #[test]
+    fn should_follow_resize_policy() {
+        let mut arr = ArrayStack::new();
+        arr.set_resize_policy(ResizePolicy::new(4, Some(8)));
+        for i in 0..5 {
+            arr.push(i);
+        }
+        // 1 -> 4 -> 16
+        assert_eq!(arr.capacity(), 16);
+        arr.pop();
+        arr.pop();
+        arr.pop();
+        assert_eq!(arr.capacity(), 8);
+
+        arr.set_resize_policy(ResizePolicy::default().without_shrinking());
+        arr.reserve(100);
+        let capacity = arr.capacity();
+        while arr.pop().is_some() {}
+        assert_eq!(arr.capacity(), capacity);
+        arr.shrink_to_fit();
+        assert_eq!(arr.capacity(), 0);
+    }
+
+    #[test]
+    fn should_compare_elements_regardless_of_layout() {
+        let mut arr: ArrayStack<_> = (0..10).collect();
+        arr.reserve(100);
+        let other: ArrayStack<_> = (0..10).collect();
+        assert_ne!(arr.capacity(), other.capacity());
+        test_utils::assert_compare_by_elements(arr, other);
+    }
+
+    #[test]
+    fn should_index_elements() {
+        let mut arr: ArrayStack<_> = (0..10).collect();
+        arr[3] = 30;
+        assert_eq!(arr[3], 30);
+        assert_eq!(arr[9], 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_when_indexing_out_of_bounds() {
+        let arr: ArrayStack<_> = (0..10).collect();
+        let _ = arr[10];
+    }
+
     #[test]
     fn should_drop_elements() {
         struct DropWatcher<'a>(&'a mut bool);
@@ -2,9 +2,17 @@
 //!
 //! See Chapter 2.5
 
-use crate::array_based_lists::array_stack::ArrayStack;
+use crate::array_based_lists::array_stack::{self, ArrayStack};
+use crate::array_based_lists::range_of;
+use crate::error::{InsertError, OdsError};
+use crate::list::List;
+use core::iter::{Chain, FromIterator, FusedIterator, Rev};
+use core::ops::RangeBounds;
 
 /// DualArrayDeque: Building a Deque from Two Stacks
+///
+/// The first elements are stored in reverse order in `front`,
+/// the following ones in order in `back`.
 pub struct DualArrayDeque<T> {
     front: ArrayStack<T>,
     back: ArrayStack<T>,
@@ -25,6 +33,393 @@ impl<T> DualArrayDeque<T> {
     pub fn is_empty(&self) -> bool {
         self.front.is_empty() && self.back.is_empty()
     }
+
+    /// Get a reference to element at given index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let front_len = self.front.len();
+        if index < front_len {
+            self.front.get(front_len - index - 1)
+        } else {
+            self.back.get(index - front_len)
+        }
+    }
+
+    /// Get a mutable reference to element at given index
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let front_len = self.front.len();
+        if index < front_len {
+            self.front.get_mut(front_len - index - 1)
+        } else {
+            self.back.get_mut(index - front_len)
+        }
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// # Panics
+    /// Panics if `index` > `len`
+    pub fn add(&mut self, index: usize, element: T) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+        let len = self.len();
+        if index > len {
+            assert_failed(index, len);
+        }
+
+        let front_len = self.front.len();
+        if index < front_len {
+            self.front.add(front_len - index, element);
+        } else {
+            self.back.add(index - front_len, element);
+        }
+        self.balance();
+    }
+
+    /// Remove and return element at given index, shifting all following elements to the left
+    ///
+    /// Returns `None` if `index` >= `len`
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let front_len = self.front.len();
+        let element = if index < front_len {
+            self.front.remove(front_len - index - 1)
+        } else {
+            self.back.remove(index - front_len)
+        };
+        self.balance();
+        Some(element)
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// Hands the element back if `index` > `len` or if the backing stacks cannot grow,
+    /// leaving the deque unchanged.
+    pub fn try_add(&mut self, index: usize, element: T) -> Result<(), InsertError<T>> {
+        let len = self.len();
+        if index > len {
+            let error = OdsError::IndexOutOfBounds { index, len };
+            return Err(InsertError::new(error, element));
+        }
+
+        let front_len = self.front.len();
+        if index < front_len {
+            self.front.try_add(front_len - index, element)?;
+        } else {
+            self.back.try_add(index - front_len, element)?;
+        }
+        if let Err(error) = self.try_balance() {
+            let element = if index < front_len {
+                self.front.remove(front_len - index)
+            } else {
+                self.back.remove(index - front_len)
+            };
+            return Err(InsertError::new(error, element));
+        }
+        Ok(())
+    }
+
+    /// Remove and return element at given index, shifting all following elements to the left
+    ///
+    /// Fails if `index` >= `len` or if the backing stacks cannot grow to rebalance,
+    /// leaving the deque unchanged.
+    pub fn try_remove(&mut self, index: usize) -> Result<T, OdsError> {
+        let len = self.len();
+        if index >= len {
+            return Err(OdsError::IndexOutOfBounds { index, len });
+        }
+
+        let front_len = self.front.len();
+        let element = if index < front_len {
+            self.front.remove(front_len - index - 1)
+        } else {
+            self.back.remove(index - front_len)
+        };
+        if let Err(error) = self.try_balance() {
+            // The stack holding the element still has room for it, even if it shrank
+            if index < front_len {
+                self.front.add(front_len - index - 1, element);
+            } else {
+                self.back.add(index - front_len, element);
+            }
+            return Err(error);
+        }
+        Ok(element)
+    }
+
+    /// Insert given element at the back of the deque
+    ///
+    /// Hands the element back if the backing stack cannot grow.
+    pub fn try_push(&mut self, element: T) -> Result<(), InsertError<T>> {
+        self.try_add(self.len(), element)
+    }
+
+    /// Make room for at least `additional` more elements at either end of the deque
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), OdsError> {
+        self.front.try_reserve(additional)?;
+        self.back.try_reserve(additional)
+    }
+
+    /// Iterate over references to the elements, from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.front.iter().rev().chain(self.back.iter()),
+        }
+    }
+
+    /// Iterate over mutable references to the elements, from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            inner: self.front.iter_mut().rev().chain(self.back.iter_mut()),
+        }
+    }
+
+    /// Drop all the elements, keeping the allocated capacity
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+    }
+
+    /// Keep the first `len` elements and drop the others, keeping the allocated capacity
+    ///
+    /// Has no effect if `len` is greater than the deque length.
+    /// The stacks are rebalanced by the next `add` or `remove`.
+    pub fn truncate(&mut self, len: usize) {
+        let front_len = self.front.len();
+        if len <= front_len {
+            self.back.clear();
+            self.front.drain(..front_len - len);
+        } else {
+            self.back.truncate(len - front_len);
+        }
+    }
+
+    /// Remove the elements in given range, yielding them in order
+    ///
+    /// The allocated capacity is kept and the stacks are rebalanced by the next `add` or `remove`.
+    /// The elements that are not consumed are dropped with the iterator.
+    ///
+    /// # Panics
+    /// Panics if the range is decreasing or ends after `len`
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let range = range_of(range, self.len());
+        let front_len = self.front.len();
+        let (front_start, front_end) = (
+            usize::min(range.start, front_len),
+            usize::min(range.end, front_len),
+        );
+        let (back_start, back_end) = (
+            usize::max(range.start, front_len) - front_len,
+            usize::max(range.end, front_len) - front_len,
+        );
+        let front = self
+            .front
+            .drain(front_len - front_end..front_len - front_start);
+        let back = self.back.drain(back_start..back_end);
+        Drain {
+            inner: front.rev().chain(back),
+        }
+    }
+
+    /// Redistribute the elements if one stack holds more than three times the other
+    fn balance(&mut self) {
+        if let Some((small, large, count)) = self.unbalanced() {
+            small.reserve(count);
+            small.add_all(0, large.drain(..count).rev());
+        }
+    }
+
+    /// Redistribute the elements like `balance`
+    ///
+    /// Fails, leaving the stacks as is, if the smaller one cannot grow.
+    fn try_balance(&mut self) -> Result<(), OdsError> {
+        if let Some((small, large, count)) = self.unbalanced() {
+            small.try_reserve(count)?;
+            small.add_all(0, large.drain(..count).rev());
+        }
+        Ok(())
+    }
+
+    /// The smaller stack, the larger one and the number of elements to move from the larger
+    /// to the smaller, if one stack holds more than three times the other
+    ///
+    /// Both stacks have their bottom in the middle of the deque: the `count` bottom elements
+    /// of `large` are to be moved to the bottom of `small`.
+    fn unbalanced(&mut self) -> Option<(&mut ArrayStack<T>, &mut ArrayStack<T>, usize)> {
+        let len = self.len();
+        let (small, large) = if 3 * self.front.len() < self.back.len() {
+            (&mut self.front, &mut self.back)
+        } else if 3 * self.back.len() < self.front.len() {
+            (&mut self.back, &mut self.front)
+        } else {
+            return None;
+        };
+        let count = len / 2 - small.len();
+        if count == 0 {
+            return None;
+        }
+        Some((small, large, count))
+    }
+}
+
+/// An iterator over references to the elements of a `DualArrayDeque`
+pub struct Iter<'a, T> {
+    inner: Chain<Rev<array_stack::Iter<'a, T>>, array_stack::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over mutable references to the elements of a `DualArrayDeque`
+pub struct IterMut<'a, T> {
+    inner: Chain<Rev<array_stack::IterMut<'a, T>>, array_stack::IterMut<'a, T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a `DualArrayDeque`
+pub struct IntoIter<T> {
+    inner: Chain<Rev<array_stack::IntoIter<T>>, array_stack::IntoIter<T>>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// A draining iterator over a range of the elements of a `DualArrayDeque`
+pub struct Drain<'a, T> {
+    inner: Chain<Rev<array_stack::Drain<'a, T>>, array_stack::Drain<'a, T>>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T> IntoIterator for DualArrayDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            inner: self.front.into_iter().rev().chain(self.back),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DualArrayDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DualArrayDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for DualArrayDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut arr = Self::new();
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T> Extend<T> for DualArrayDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.back.extend(iter);
+        self.balance();
+    }
 }
 
 impl<T> Default for DualArrayDeque<T> {
@@ -32,3 +427,239 @@ impl<T> Default for DualArrayDeque<T> {
         Self::new()
     }
 }
+
+impl<T> List for DualArrayDeque<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        DualArrayDeque::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        DualArrayDeque::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        DualArrayDeque::get_mut(self, index)
+    }
+
+    fn add(&mut self, index: usize, item: T) {
+        DualArrayDeque::add(self, index, item)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        DualArrayDeque::remove(self, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DualArrayDeque;
+    use crate::error::OdsError;
+    use crate::list::List;
+    use std::collections::VecDeque;
+
+    fn assert_balanced<T>(arr: &DualArrayDeque<T>) {
+        let (front, back) = (arr.front.len(), arr.back.len());
+        if arr.len() >= 2 {
+            assert!(3 * front >= back, "front: {}, back: {}", front, back);
+            assert!(3 * back >= front, "front: {}, back: {}", front, back);
+        }
+    }
+
+    #[test]
+    fn should_add_and_remove_elements() {
+        let mut arr = DualArrayDeque::new();
+        arr.add(0, "foo");
+        arr.add(1, "bar");
+        arr.add(1, "foobar");
+        assert_eq!(arr.get(0), Some(&"foo"));
+        assert_eq!(arr.get(1), Some(&"foobar"));
+        assert_eq!(arr.get(2), Some(&"bar"));
+        assert_eq!(arr.get(3), None);
+        assert_eq!(arr.remove(0), Some("foo"));
+        assert_eq!(arr.remove(0), Some("foobar"));
+        assert_eq!(arr.remove(0), Some("bar"));
+        assert_eq!(arr.remove(0), None);
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn should_set_elements() {
+        let mut arr = DualArrayDeque::new();
+        for i in 0..10 {
+            arr.add(0, i);
+        }
+        for i in 0..10 {
+            assert_eq!(arr.set(i, i * 10), 9 - i);
+        }
+        for i in 0..10 {
+            assert_eq!(arr.get(i), Some(&(i * 10)));
+        }
+    }
+
+    #[test]
+    fn should_reallocate_properly() {
+        // Compare to reference impl: VecDeque
+        let mut arr = DualArrayDeque::new();
+        let mut deque = VecDeque::new();
+        const LEN: usize = 100;
+        for i in 0..LEN {
+            arr.add(i, i);
+            deque.insert(i, i);
+            for j in 0..i {
+                arr.add(j, j);
+                deque.insert(j, j);
+                assert_balanced(&arr);
+            }
+            for j in 0..i {
+                assert_eq!(arr.get(j), deque.get(j));
+            }
+            for j in (0..i).rev() {
+                assert_eq!(arr.remove(j), deque.remove(j));
+                assert_balanced(&arr);
+            }
+        }
+        for _ in 0..LEN {
+            assert_eq!(arr.remove(0), deque.remove(0));
+            assert_balanced(&arr);
+        }
+        assert_eq!(arr.remove(0), None);
+        assert_eq!(deque.remove(0), None);
+    }
+
+    #[test]
+    fn should_stay_balanced_when_growing_at_one_end() {
+        let mut arr = DualArrayDeque::new();
+        let mut deque = VecDeque::new();
+        for i in 0..100 {
+            arr.add(arr.len(), i);
+            deque.push_back(i);
+            assert_balanced(&arr);
+        }
+        for _ in 0..100 {
+            assert_eq!(arr.remove(arr.len() - 1), deque.pop_back());
+            assert_balanced(&arr);
+        }
+    }
+
+    #[test]
+    fn should_iterate_over_elements() {
+        let mut arr: DualArrayDeque<_> = (5..10).collect();
+        let mut deque: VecDeque<_> = (5..10).collect();
+        for i in (0..5).rev() {
+            arr.add(0, i);
+            deque.push_front(i);
+        }
+        arr.extend(10..20);
+        deque.extend(10..20);
+        assert_balanced(&arr);
+        assert!(arr.iter().eq(deque.iter()));
+        assert!(arr.iter().rev().eq(deque.iter().rev()));
+        assert_eq!(arr.iter().len(), deque.len());
+        for element in &mut arr {
+            *element *= 2;
+        }
+        for element in arr.iter_mut().rev().take(3) {
+            *element += 1;
+        }
+        for element in deque.iter_mut() {
+            *element *= 2;
+        }
+        for element in deque.iter_mut().rev().take(3) {
+            *element += 1;
+        }
+        assert!((&arr).into_iter().eq(deque.iter()));
+        let mut iter = arr.into_iter();
+        assert_eq!(iter.next(), deque.pop_front());
+        assert_eq!(iter.next_back(), deque.pop_back());
+        assert_eq!(iter.len(), deque.len());
+        assert!(iter.eq(deque.into_iter()));
+    }
+
+    #[test]
+    fn should_clear_and_truncate_elements() {
+        for len in 0..25 {
+            let mut arr: DualArrayDeque<_> = (0..10).collect();
+            let mut deque: VecDeque<_> = (0..10).collect();
+            for i in 10..20 {
+                arr.add(0, i);
+                deque.push_front(i);
+            }
+            arr.truncate(len);
+            deque.truncate(len);
+            assert!(arr.iter().eq(deque.iter()));
+            arr.add(arr.len() / 2, 42);
+            deque.insert(deque.len() / 2, 42);
+            assert!(arr.iter().eq(deque.iter()));
+            assert_balanced(&arr);
+        }
+        let mut arr: DualArrayDeque<_> = (0..10).collect();
+        arr.clear();
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn should_drain_elements() {
+        for start in 0..20 {
+            for end in start..20 {
+                let mut arr: DualArrayDeque<_> = (0..10).collect();
+                let mut deque: VecDeque<_> = (0..10).collect();
+                for i in 10..20 {
+                    arr.add(0, i);
+                    deque.push_front(i);
+                }
+                assert!(arr.drain(start..end).eq(deque.drain(start..end)));
+                assert!(arr.iter().eq(deque.iter()));
+                let mut drain = arr.drain(..);
+                assert_eq!(drain.next_back(), deque.pop_back());
+                assert!(drain.eq(deque.drain(..)));
+                assert!(arr.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn should_try_to_add_and_remove_elements() {
+        let mut arr = DualArrayDeque::new();
+        arr.try_reserve(10).unwrap();
+        for i in 0..10 {
+            arr.try_add(i / 2, i).unwrap();
+            assert_balanced(&arr);
+        }
+        assert!(arr.iter().copied().eq([1, 3, 5, 7, 9, 8, 6, 4, 2, 0]));
+        let error = arr.try_add(11, 11).unwrap_err();
+        assert_eq!(
+            error.error(),
+            OdsError::IndexOutOfBounds { index: 11, len: 10 }
+        );
+        assert_eq!(error.into_element(), 11);
+        assert_eq!(arr.try_remove(0), Ok(1));
+        assert_eq!(
+            arr.try_remove(9),
+            Err(OdsError::IndexOutOfBounds { index: 9, len: 9 })
+        );
+        arr.try_push(10).unwrap();
+        assert_eq!(arr.get(9), Some(&10));
+        assert_eq!(
+            arr.try_reserve(usize::MAX),
+            Err(OdsError::capacity_overflow::<usize>())
+        );
+    }
+
+    #[test]
+    fn should_drop_elements() {
+        struct DropWatcher<'a>(&'a mut bool);
+        impl Drop for DropWatcher<'_> {
+            fn drop(&mut self) {
+                *self.0 = true;
+            }
+        }
+        let mut dropped = false;
+        {
+            let mut arr = DualArrayDeque::new();
+            arr.add(0, DropWatcher(&mut dropped));
+        }
+        assert!(dropped);
+    }
+}
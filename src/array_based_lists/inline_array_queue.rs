@@ -0,0 +1,281 @@
+//! # InlineArrayQueue: A Fixed-Capacity ArrayQueue Without Allocation
+//!
+//! See Chapter 2.3
+
+use crate::array_based_lists::ring::{self, Iter, IterMut};
+use crate::error::{InsertError, OdsError};
+use crate::queue::Queue;
+use core::fmt;
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+
+/// InlineArrayQueue: an ArrayQueue storing at most `N` elements inline
+///
+/// The elements live in the queue itself, so it never allocates.
+/// Inserting in a full queue hands the element back.
+pub struct InlineArrayQueue<T, const N: usize> {
+    buf: [MaybeUninit<T>; N],
+    len: usize,
+    offset: usize,
+}
+
+impl<T, const N: usize> InlineArrayQueue<T, N> {
+    /// Create a new, empty, InlineArrayQueue
+    pub const fn new() -> Self {
+        InlineArrayQueue {
+            // An array of `MaybeUninit` does not need to be initialized
+            buf: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+            len: 0,
+            offset: 0,
+        }
+    }
+
+    /// The array queue length
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the array queue empty ?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Is the array queue full ?
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// The maximum number of elements held by the array queue
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Insert given element at the end of the queue
+    ///
+    /// Hands the element back if the queue is full.
+    pub fn try_add(&mut self, element: T) -> Result<(), InsertError<T>> {
+        let len = self.len;
+        if len == N {
+            let error = OdsError::CapacityExceeded { capacity: N };
+            return Err(InsertError::new(error, element));
+        }
+        self.buf[ring::wrap(self.offset + len, N)] = MaybeUninit::new(element);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Remove and return the element in front of the queue
+    ///
+    /// Fails if the queue is empty
+    pub fn try_remove(&mut self) -> Result<T, OdsError> {
+        self.remove().ok_or(OdsError::Empty)
+    }
+
+    /// Check there is room for `additional` more elements
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), OdsError> {
+        if additional <= N - self.len {
+            Ok(())
+        } else {
+            Err(OdsError::CapacityExceeded { capacity: N })
+        }
+    }
+
+    /// Remove and return the element in front of the queue
+    pub fn remove(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let element = unsafe { self.buf[self.offset].as_ptr().read() };
+        self.offset = ring::wrap(self.offset + 1, N);
+        self.len -= 1;
+        Some(element)
+    }
+
+    /// Iterate over references to the elements, from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(unsafe { ring::as_slices(&self.buf, self.offset, self.len) })
+    }
+
+    /// Iterate over mutable references to the elements, from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(unsafe { ring::as_mut_slices(&mut self.buf, self.offset, self.len) })
+    }
+
+    /// Drop all the elements
+    pub fn clear(&mut self) {
+        let len = mem::replace(&mut self.len, 0);
+        unsafe {
+            let (front, back) = ring::as_mut_slices(&mut self.buf, self.offset, len);
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a InlineArrayQueue<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut InlineArrayQueue<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for InlineArrayQueue<T, N> {
+    fn clone(&self) -> Self {
+        let mut arr = Self::new();
+        for element in self.iter() {
+            // Cannot fail: `arr` has the same capacity as `self`
+            let _ = arr.try_add(element.clone());
+        }
+        arr
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for InlineArrayQueue<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const N: usize> Default for InlineArrayQueue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for InlineArrayQueue<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Queue for InlineArrayQueue<T, N> {
+    type Item = T;
+
+    /// Insert given element at the end of the queue
+    ///
+    /// # Panics
+    /// Panics if the queue is full, see `try_add`
+    fn add(&mut self, item: T) {
+        if self.try_add(item).is_err() {
+            panic!("queue should not be full (capacity is {})", N);
+        }
+    }
+
+    fn remove(&mut self) -> Option<T> {
+        InlineArrayQueue::remove(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InlineArrayQueue;
+    use crate::error::OdsError;
+    use crate::queue::Queue;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn should_add_until_full() {
+        let mut arr = InlineArrayQueue::<_, 3>::new();
+        assert_eq!(arr.try_add("foo"), Ok(()));
+        assert_eq!(arr.try_add("bar"), Ok(()));
+        assert_eq!(arr.try_add("foobar"), Ok(()));
+        assert!(arr.is_full());
+        let error = arr.try_add("rejected").unwrap_err();
+        assert_eq!(error.error(), OdsError::CapacityExceeded { capacity: 3 });
+        assert_eq!(error.into_element(), "rejected");
+        assert_eq!(
+            arr.try_reserve(1),
+            Err(OdsError::CapacityExceeded { capacity: 3 })
+        );
+        assert_eq!(arr.remove(), Some("foo"));
+        assert_eq!(arr.try_add("baz"), Ok(()));
+        assert!(arr.iter().eq(&["bar", "foobar", "baz"]));
+        assert_eq!(arr.remove(), Some("bar"));
+        assert_eq!(arr.remove(), Some("foobar"));
+        assert_eq!(arr.remove(), Some("baz"));
+        assert_eq!(arr.remove(), None);
+    }
+
+    #[test]
+    fn should_wrap_around_like_vec_deque() {
+        let mut arr = InlineArrayQueue::<_, 5>::new();
+        let mut deque = VecDeque::new();
+        for i in 0..100 {
+            if i % 3 == 2 {
+                assert_eq!(arr.remove(), deque.pop_front());
+            } else if arr.try_add(i).is_ok() {
+                deque.push_back(i);
+            } else {
+                assert_eq!(deque.len(), 5);
+            }
+            assert!(arr.iter().eq(deque.iter()));
+            assert!(arr.iter().rev().eq(deque.iter().rev()));
+        }
+    }
+
+    #[test]
+    fn should_reject_elements_without_capacity() {
+        let mut arr = InlineArrayQueue::<_, 0>::new();
+        assert!(arr.is_full());
+        assert_eq!(arr.try_add(1).unwrap_err().into_element(), 1);
+        assert_eq!(arr.try_reserve(0), Ok(()));
+        assert_eq!(arr.remove(), None);
+        assert_eq!(arr.try_remove(), Err(OdsError::Empty));
+        assert_eq!(arr.iter().next(), None);
+    }
+
+    #[test]
+    fn should_be_a_queue() {
+        let mut arr = InlineArrayQueue::<_, 4>::new();
+        arr.add(0);
+        arr.add(1);
+        assert_eq!(Queue::remove(&mut arr), Some(0));
+        assert_eq!(Queue::remove(&mut arr), Some(1));
+        assert_eq!(Queue::remove(&mut arr), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "queue should not be full (capacity is 2)")]
+    fn should_panic_when_adding_to_a_full_queue() {
+        let mut arr = InlineArrayQueue::<_, 2>::new();
+        for i in 0..3 {
+            arr.add(i);
+        }
+    }
+
+    #[test]
+    fn should_drop_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let mut arr = InlineArrayQueue::<_, 4>::new();
+        for _ in 0..4 {
+            arr.add(Rc::clone(&watcher));
+        }
+        arr.remove();
+        arr.add(Rc::clone(&watcher));
+        let clone = arr.clone();
+        assert_eq!(Rc::strong_count(&watcher), 9);
+        drop(arr);
+        drop(clone);
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+}
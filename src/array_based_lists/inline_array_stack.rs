@@ -0,0 +1,369 @@
+//! # InlineArrayStack: A Fixed-Capacity ArrayStack Without Allocation
+//!
+//! See Chapter 2.1
+
+use crate::error::{InsertError, OdsError};
+use crate::stack::Stack;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::{ptr, slice};
+
+/// InlineArrayStack: an ArrayStack storing at most `N` elements inline
+///
+/// The elements live in the stack itself, so it never allocates.
+/// Inserting in a full stack hands the element back.
+pub struct InlineArrayStack<T, const N: usize> {
+    buf: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> InlineArrayStack<T, N> {
+    /// Create a new, empty, InlineArrayStack
+    pub const fn new() -> Self {
+        InlineArrayStack {
+            // An array of `MaybeUninit` does not need to be initialized
+            buf: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+            len: 0,
+        }
+    }
+
+    /// The array stack length
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the array stack empty ?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Is the array stack full ?
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// The maximum number of elements held by the array stack
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Get a reference to element at given index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    /// Get a mutable reference to element at given index
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(index)
+    }
+
+    /// The elements, from bottom to top
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.buf.as_ptr() as *const T, self.len) }
+    }
+
+    /// The elements, from bottom to top
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// Hands the element back if `index` > `len` or if the stack is full.
+    pub fn try_add(&mut self, index: usize, element: T) -> Result<(), InsertError<T>> {
+        let len = self.len;
+        if index > len {
+            let error = OdsError::IndexOutOfBounds { index, len };
+            return Err(InsertError::new(error, element));
+        }
+        if len == N {
+            let error = OdsError::CapacityExceeded { capacity: N };
+            return Err(InsertError::new(error, element));
+        }
+
+        unsafe {
+            let p = self.buf.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(1), len - index);
+            p.write(MaybeUninit::new(element));
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Remove at returns element at given index, shifting all following elements to the left
+    ///
+    /// # Panics
+    /// Panics if `index` >= `len`
+    pub fn remove(&mut self, index: usize) -> T {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("removal index (is {}) should be < len (is {})", index, len);
+        }
+        let len = self.len;
+        if index >= len {
+            assert_failed(index, len);
+        }
+        let element;
+        unsafe {
+            let p = self.buf.as_mut_ptr().add(index);
+            element = p.read().assume_init();
+            ptr::copy(p.add(1), p, len - index - 1);
+        }
+        self.len -= 1;
+        element
+    }
+
+    /// Remove and return the element at given index, shifting all following elements to the left
+    ///
+    /// Fails if `index` >= `len`
+    pub fn try_remove(&mut self, index: usize) -> Result<T, OdsError> {
+        let len = self.len;
+        if index < len {
+            Ok(self.remove(index))
+        } else {
+            Err(OdsError::IndexOutOfBounds { index, len })
+        }
+    }
+
+    /// Insert given element on top of the stack
+    ///
+    /// Hands the element back if the stack is full.
+    pub fn try_push(&mut self, element: T) -> Result<(), InsertError<T>> {
+        self.try_add(self.len, element)
+    }
+
+    /// Check there is room for `additional` more elements
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), OdsError> {
+        if additional <= N - self.len {
+            Ok(())
+        } else {
+            Err(OdsError::CapacityExceeded { capacity: N })
+        }
+    }
+
+    /// Remove and return the top element
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len;
+        if len > 0 {
+            Some(self.remove(len - 1))
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over references to the elements, from bottom to top
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Iterate over mutable references to the elements, from bottom to top
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Drop all the elements
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keep the first `len` elements and drop the others
+    ///
+    /// Has no effect if `len` is greater than the stack length.
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len;
+        if len >= old_len {
+            return;
+        }
+        self.len = len;
+        unsafe {
+            let p = self.buf.as_mut_ptr().add(len) as *mut T;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(p, old_len - len));
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a InlineArrayStack<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut InlineArrayStack<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for InlineArrayStack<T, N> {
+    fn clone(&self) -> Self {
+        let mut arr = Self::new();
+        for element in self.iter() {
+            // Cannot fail: `arr` has the same capacity as `self`
+            let _ = arr.try_push(element.clone());
+        }
+        arr
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for InlineArrayStack<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const N: usize> Default for InlineArrayStack<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for InlineArrayStack<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Stack for InlineArrayStack<T, N> {
+    type Item = T;
+
+    /// Insert given element on top of the stack
+    ///
+    /// # Panics
+    /// Panics if the stack is full, see `try_push`
+    fn push(&mut self, item: T) {
+        if self.try_push(item).is_err() {
+            panic!("stack should not be full (capacity is {})", N);
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        InlineArrayStack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InlineArrayStack;
+    use crate::error::OdsError;
+    use crate::stack::Stack;
+
+    #[test]
+    fn should_push_until_full() {
+        let mut arr = InlineArrayStack::<_, 3>::new();
+        assert_eq!(arr.try_push("foo"), Ok(()));
+        assert_eq!(arr.try_push("bar"), Ok(()));
+        assert_eq!(arr.try_push("foobar"), Ok(()));
+        assert!(arr.is_full());
+        let error = arr.try_push("rejected").unwrap_err();
+        assert_eq!(error.error(), OdsError::CapacityExceeded { capacity: 3 });
+        assert_eq!(error.into_element(), "rejected");
+        assert_eq!(arr.as_slice(), ["foo", "bar", "foobar"]);
+        assert_eq!(arr.pop(), Some("foobar"));
+        assert_eq!(arr.try_push("baz"), Ok(()));
+        assert_eq!(arr.pop(), Some("baz"));
+        assert_eq!(arr.pop(), Some("bar"));
+        assert_eq!(arr.pop(), Some("foo"));
+        assert_eq!(arr.pop(), None);
+    }
+
+    #[test]
+    fn should_add_and_remove_elements() {
+        let mut arr = InlineArrayStack::<_, 8>::new();
+        for i in 0..4 {
+            arr.try_add(0, i).unwrap();
+        }
+        arr.try_add(2, 10).unwrap();
+        assert_eq!(arr.as_slice(), [3, 2, 10, 1, 0]);
+        assert_eq!(arr.remove(1), 2);
+        *arr.get_mut(0).unwrap() = 30;
+        assert_eq!(arr.get(0), Some(&30));
+        assert_eq!(arr.get(4), None);
+        assert!(arr.iter().copied().eq([30, 10, 1, 0]));
+    }
+
+    #[test]
+    fn should_reject_elements_without_capacity() {
+        let mut arr = InlineArrayStack::<_, 0>::new();
+        assert!(arr.is_full());
+        assert_eq!(arr.try_push(1).unwrap_err().into_element(), 1);
+        assert_eq!(
+            arr.try_reserve(1),
+            Err(OdsError::CapacityExceeded { capacity: 0 })
+        );
+        assert_eq!(arr.pop(), None);
+    }
+
+    #[test]
+    fn should_be_a_stack() {
+        let mut arr = InlineArrayStack::<_, 4>::new();
+        arr.push(0);
+        arr.push(1);
+        assert_eq!(Stack::pop(&mut arr), Some(1));
+        assert_eq!(Stack::pop(&mut arr), Some(0));
+        assert_eq!(Stack::pop(&mut arr), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "stack should not be full (capacity is 2)")]
+    fn should_panic_when_pushing_on_a_full_stack() {
+        let mut arr = InlineArrayStack::<_, 2>::new();
+        for i in 0..3 {
+            arr.push(i);
+        }
+    }
+
+    #[test]
+    fn should_reject_indices_out_of_bounds() {
+        let mut arr = InlineArrayStack::<_, 4>::new();
+        arr.push(0);
+        let error = arr.try_add(2, 1).unwrap_err();
+        assert_eq!(
+            error.error(),
+            OdsError::IndexOutOfBounds { index: 2, len: 1 }
+        );
+        assert_eq!(
+            arr.try_remove(1),
+            Err(OdsError::IndexOutOfBounds { index: 1, len: 1 })
+        );
+        assert_eq!(arr.try_remove(0), Ok(0));
+        assert_eq!(arr.try_reserve(4), Ok(()));
+        assert_eq!(
+            arr.try_reserve(5),
+            Err(OdsError::CapacityExceeded { capacity: 4 })
+        );
+    }
+
+    #[test]
+    fn should_drop_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let mut arr = InlineArrayStack::<_, 10>::new();
+        for _ in 0..10 {
+            arr.push(Rc::clone(&watcher));
+        }
+        let rejected = arr.try_push(Rc::clone(&watcher));
+        assert_eq!(Rc::strong_count(&watcher), 12);
+        drop(rejected);
+        let clone = arr.clone();
+        arr.truncate(4);
+        assert_eq!(Rc::strong_count(&watcher), 15);
+        drop(arr);
+        drop(clone);
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+}
@@ -0,0 +1,322 @@
+//! # MinDeque: A Deque Reporting its Minimum
+//!
+//! See Chapter 3, Exercise 3.17
+
+use crate::array_based_lists::array_stack::{self, ArrayStack};
+use crate::array_based_lists::min_stack::MinStack;
+use crate::deque::Deque;
+use core::fmt;
+use core::iter::{Chain, FromIterator, FusedIterator, Rev};
+use core::mem;
+
+/// MinDeque: a deque whose minimum is known in O(1)
+///
+/// The first elements are stored in reverse order in `front`, the following ones in order
+/// in `back`. When removing from an empty stack, the elements of the other stack are split
+/// in halves between both stacks: the n elements moved are followed by at least n / 2
+/// removals before the next split, so removals are in O(1) amortized.
+pub struct MinDeque<T> {
+    front: MinStack<T>,
+    back: MinStack<T>,
+}
+
+impl<T> MinDeque<T> {
+    /// Create a new MinDeque
+    pub fn new() -> Self {
+        MinDeque {
+            front: MinStack::new(),
+            back: MinStack::new(),
+        }
+    }
+
+    /// The deque length
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Is the deque empty ?
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty() && self.back.is_empty()
+    }
+
+    /// Get a reference to the element in front of the deque
+    pub fn peek_first(&self) -> Option<&T> {
+        self.front.peek().or_else(|| self.back.iter().next())
+    }
+
+    /// Get a reference to the element at the end of the deque
+    pub fn peek_last(&self) -> Option<&T> {
+        self.back.peek().or_else(|| self.front.iter().next())
+    }
+
+    /// Iterate over references to the elements, from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.front.iter().rev().chain(self.back.iter()),
+        }
+    }
+
+    /// Drop all the elements
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+    }
+}
+
+impl<T: Ord> MinDeque<T> {
+    /// Get a reference to the minimum element, in O(1)
+    pub fn min(&self) -> Option<&T> {
+        match (self.front.min(), self.back.min()) {
+            (Some(front), Some(back)) => Some(front.min(back)),
+            (front, back) => front.or(back),
+        }
+    }
+
+    /// Insert given element in front of the deque
+    pub fn add_first(&mut self, element: T) {
+        self.front.push(element);
+    }
+
+    /// Insert given element at the end of the deque
+    pub fn add_last(&mut self, element: T) {
+        self.back.push(element);
+    }
+
+    /// Remove and return the element in front of the deque, in O(1) amortized
+    pub fn remove_first(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            split(&mut self.back, &mut self.front);
+        }
+        self.front.pop()
+    }
+
+    /// Remove and return the element at the end of the deque, in O(1) amortized
+    pub fn remove_last(&mut self) -> Option<T> {
+        if self.back.is_empty() {
+            split(&mut self.front, &mut self.back);
+        }
+        self.back.pop()
+    }
+}
+
+/// Move the bottom half of `full` to the empty stack `empty`
+///
+/// Both stacks have their bottom in the middle of the deque, so the bottom elements of
+/// `full` are pushed on `empty` from the middle outwards. The top half of `full`,
+/// rounded down, is kept.
+fn split<T: Ord>(full: &mut MinStack<T>, empty: &mut MinStack<T>) {
+    let len = full.len();
+    // The minimums of `full` refer to the moved elements: rebuild it too
+    let mut elements: ArrayStack<T> = mem::take(full).into_iter().collect();
+    empty.extend(elements.drain(..len - len / 2).rev());
+    full.extend(elements);
+}
+
+/// An iterator over references to the elements of a `MinDeque`
+pub struct Iter<'a, T> {
+    inner: Chain<Rev<array_stack::Iter<'a, T>>, array_stack::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a MinDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Self::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T: Ord> Extend<T> for MinDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.back.extend(iter);
+    }
+}
+
+impl<T: Clone> Clone for MinDeque<T> {
+    fn clone(&self) -> Self {
+        MinDeque {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MinDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for MinDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for MinDeque<T> {}
+
+impl<T> Default for MinDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Deque for MinDeque<T> {
+    type Item = T;
+
+    fn add_first(&mut self, item: T) {
+        MinDeque::add_first(self, item)
+    }
+
+    fn add_last(&mut self, item: T) {
+        MinDeque::add_last(self, item)
+    }
+
+    fn remove_first(&mut self) -> Option<T> {
+        MinDeque::remove_first(self)
+    }
+
+    fn remove_last(&mut self) -> Option<T> {
+        MinDeque::remove_last(self)
+    }
+
+    fn peek_first(&self) -> Option<&T> {
+        MinDeque::peek_first(self)
+    }
+
+    fn peek_last(&self) -> Option<&T> {
+        MinDeque::peek_last(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MinDeque;
+    use crate::array_based_lists::test_utils::Counted;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn should_report_the_minimum() {
+        let mut deque = MinDeque::new();
+        assert_eq!(deque.min(), None);
+        deque.add_last(3);
+        deque.add_last(1);
+        deque.add_first(4);
+        deque.add_first(2);
+        assert_eq!(deque.min(), Some(&1));
+        assert_eq!(deque.remove_last(), Some(1));
+        assert_eq!(deque.min(), Some(&2));
+        assert_eq!(deque.remove_first(), Some(2));
+        assert_eq!(deque.min(), Some(&3));
+        assert_eq!(deque.peek_first(), Some(&4));
+        assert_eq!(deque.peek_last(), Some(&3));
+        assert_eq!(deque.remove_last(), Some(3));
+        assert_eq!(deque.remove_last(), Some(4));
+        assert_eq!(deque.remove_first(), None);
+        assert_eq!(deque.remove_last(), None);
+        assert_eq!(deque.min(), None);
+    }
+
+    #[test]
+    fn should_split_stacks_when_removing() {
+        let mut deque: MinDeque<_> = (0..10).collect();
+        assert_eq!(deque.remove_first(), Some(0));
+        assert_eq!(deque.front.len(), 4);
+        assert_eq!(deque.back.len(), 5);
+        assert_eq!(deque.min(), Some(&1));
+        assert!(deque.iter().copied().eq(1..10));
+    }
+
+    #[test]
+    fn should_behave_like_vec_deque() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut min_deque = MinDeque::new();
+        let mut deque = VecDeque::new();
+        for _ in 0..10_000 {
+            match rng.gen_range(0..5) {
+                0 | 1 => {
+                    let element = rng.gen_range(0..1_000);
+                    min_deque.add_first(element);
+                    deque.push_front(element);
+                }
+                2 => {
+                    let element = rng.gen_range(0..1_000);
+                    min_deque.add_last(element);
+                    deque.push_back(element);
+                }
+                3 => assert_eq!(min_deque.remove_first(), deque.pop_front()),
+                _ => assert_eq!(min_deque.remove_last(), deque.pop_back()),
+            }
+            assert_eq!(min_deque.min(), deque.iter().min());
+            assert_eq!(min_deque.peek_first(), deque.front());
+            assert_eq!(min_deque.peek_last(), deque.back());
+            assert_eq!(min_deque.len(), deque.len());
+        }
+        assert!(min_deque.iter().eq(deque.iter()));
+        assert!(min_deque.iter().rev().eq(deque.iter().rev()));
+    }
+
+    #[test]
+    fn should_compare_in_constant_amortized_time() {
+        let comparisons = Cell::new(0);
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut deque = MinDeque::new();
+        let mut operations = 0;
+        for _ in 0..100 {
+            for _ in 0..rng.gen_range(1..1_000) {
+                let value = rng.gen();
+                deque.add_last(Counted {
+                    value,
+                    comparisons: &comparisons,
+                });
+                operations += 1;
+            }
+            // Removing at both ends moves the elements between the stacks
+            while !deque.is_empty() {
+                if rng.gen_bool(0.5) {
+                    deque.remove_first();
+                } else {
+                    deque.remove_last();
+                }
+                deque.min();
+                operations += 1;
+            }
+        }
+        // One comparison per push on a stack, each split pushing each moved element once
+        // more and being followed by as many removals, and one comparison per `min`
+        assert!(comparisons.get() <= 5 * operations);
+    }
+}
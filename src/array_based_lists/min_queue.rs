@@ -0,0 +1,255 @@
+//! # MinQueue: A Queue Reporting its Minimum
+//!
+//! See Chapter 3, Exercise 3.16
+
+use crate::array_based_lists::array_stack;
+use crate::array_based_lists::min_stack::MinStack;
+use crate::queue::Queue;
+use core::fmt;
+use core::iter::{Chain, FromIterator, FusedIterator, Rev};
+
+/// MinQueue: a queue whose minimum is known in O(1)
+///
+/// Elements are added to the `back` stack, and removed from the `front` stack, which holds
+/// the first elements in reverse order. When `front` is empty, all the elements of `back`
+/// are moved to `front`: each element is moved once, so removals are in O(1) amortized.
+pub struct MinQueue<T> {
+    front: MinStack<T>,
+    back: MinStack<T>,
+}
+
+impl<T> MinQueue<T> {
+    /// Create a new MinQueue
+    pub fn new() -> Self {
+        MinQueue {
+            front: MinStack::new(),
+            back: MinStack::new(),
+        }
+    }
+
+    /// The queue length
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Is the queue empty ?
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty() && self.back.is_empty()
+    }
+
+    /// Get a reference to the element in front of the queue
+    pub fn peek(&self) -> Option<&T> {
+        self.front.peek().or_else(|| self.back.iter().next())
+    }
+
+    /// Iterate over references to the elements, from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.front.iter().rev().chain(self.back.iter()),
+        }
+    }
+
+    /// Drop all the elements
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+    }
+}
+
+impl<T: Ord> MinQueue<T> {
+    /// Get a reference to the minimum element, in O(1)
+    pub fn min(&self) -> Option<&T> {
+        match (self.front.min(), self.back.min()) {
+            (Some(front), Some(back)) => Some(front.min(back)),
+            (front, back) => front.or(back),
+        }
+    }
+
+    /// Insert given element at the end of the queue
+    pub fn add(&mut self, element: T) {
+        self.back.push(element);
+    }
+
+    /// Remove and return the element in front of the queue, in O(1) amortized
+    pub fn remove(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            while let Some(element) = self.back.pop() {
+                self.front.push(element);
+            }
+        }
+        self.front.pop()
+    }
+}
+
+/// An iterator over references to the elements of a `MinQueue`
+pub struct Iter<'a, T> {
+    inner: Chain<Rev<array_stack::Iter<'a, T>>, array_stack::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a MinQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T: Ord> Extend<T> for MinQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.back.extend(iter);
+    }
+}
+
+impl<T: Clone> Clone for MinQueue<T> {
+    fn clone(&self) -> Self {
+        MinQueue {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MinQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for MinQueue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for MinQueue<T> {}
+
+impl<T> Default for MinQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Queue for MinQueue<T> {
+    type Item = T;
+
+    fn add(&mut self, item: T) {
+        MinQueue::add(self, item)
+    }
+
+    fn remove(&mut self) -> Option<T> {
+        MinQueue::remove(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        MinQueue::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        MinQueue::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MinQueue;
+    use crate::array_based_lists::test_utils::Counted;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn should_report_the_minimum() {
+        let mut queue = MinQueue::new();
+        assert_eq!(queue.min(), None);
+        queue.add(3);
+        queue.add(1);
+        queue.add(4);
+        assert_eq!(queue.min(), Some(&1));
+        assert_eq!(queue.remove(), Some(3));
+        queue.add(1);
+        assert_eq!(queue.remove(), Some(1));
+        // The other 1 was added after
+        assert_eq!(queue.min(), Some(&1));
+        assert_eq!(queue.remove(), Some(4));
+        assert_eq!(queue.peek(), Some(&1));
+        assert_eq!(queue.remove(), Some(1));
+        assert_eq!(queue.remove(), None);
+        assert_eq!(queue.min(), None);
+    }
+
+    #[test]
+    fn should_behave_like_vec_deque() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut queue = MinQueue::new();
+        let mut deque = VecDeque::new();
+        for _ in 0..10_000 {
+            if rng.gen_bool(0.6) {
+                let element = rng.gen_range(0..1_000);
+                queue.add(element);
+                deque.push_back(element);
+            } else {
+                assert_eq!(queue.remove(), deque.pop_front());
+            }
+            assert_eq!(queue.min(), deque.iter().min());
+            assert_eq!(queue.peek(), deque.front());
+            assert_eq!(queue.len(), deque.len());
+        }
+        assert!(queue.iter().eq(deque.iter()));
+        assert!(queue.iter().rev().eq(deque.iter().rev()));
+    }
+
+    #[test]
+    fn should_compare_in_constant_amortized_time() {
+        let comparisons = Cell::new(0);
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut queue = MinQueue::new();
+        let operations = 100_000;
+        for _ in 0..operations {
+            if rng.gen_bool(0.5) {
+                let value = rng.gen();
+                queue.add(Counted {
+                    value,
+                    comparisons: &comparisons,
+                });
+            } else {
+                queue.remove();
+            }
+            queue.min();
+        }
+        // One comparison per push on a stack, each element being pushed at most twice,
+        // and one comparison per `min`
+        assert!(comparisons.get() <= 3 * operations);
+    }
+}
@@ -0,0 +1,219 @@
+//! # MinStack: A Stack Reporting its Minimum
+//!
+//! See Chapter 3, Exercise 3.15
+
+use crate::array_based_lists::array_stack::{self, ArrayStack};
+use crate::stack::Stack;
+use core::fmt;
+use core::iter::FromIterator;
+
+/// MinStack: a stack whose minimum is known in O(1)
+///
+/// Along with the elements, the stack keeps the indices of the elements that were a minimum
+/// when pushed, the last one being the current minimum.
+pub struct MinStack<T> {
+    elements: ArrayStack<T>,
+    mins: ArrayStack<usize>,
+}
+
+impl<T> MinStack<T> {
+    /// Create a new MinStack
+    pub fn new() -> Self {
+        MinStack {
+            elements: ArrayStack::new(),
+            mins: ArrayStack::new(),
+        }
+    }
+
+    /// The stack length
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Is the stack empty ?
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Get a reference to the element at the top of the stack
+    pub fn peek(&self) -> Option<&T> {
+        self.len()
+            .checked_sub(1)
+            .and_then(|index| self.elements.get(index))
+    }
+
+    /// Get a reference to the minimum element, in O(1)
+    pub fn min(&self) -> Option<&T> {
+        self.mins
+            .len()
+            .checked_sub(1)
+            .and_then(|index| self.elements.get(self.mins[index]))
+    }
+
+    /// Iterate over references to the elements, from bottom to top
+    pub fn iter(&self) -> array_stack::Iter<'_, T> {
+        self.elements.iter()
+    }
+
+    /// Drop all the elements
+    pub fn clear(&mut self) {
+        self.elements.clear();
+        self.mins.clear();
+    }
+}
+
+impl<T: Ord> MinStack<T> {
+    /// Insert given element at the top of the stack
+    pub fn push(&mut self, element: T) {
+        let len = self.len();
+        if self.min().is_none_or(|min| element <= *min) {
+            self.mins.add(self.mins.len(), len);
+        }
+        self.elements.add(len, element);
+    }
+
+    /// Remove and return the element at the top of the stack
+    pub fn pop(&mut self) -> Option<T> {
+        let index = self.len().checked_sub(1)?;
+        let mins = self.mins.len();
+        if self.mins[mins - 1] == index {
+            self.mins.remove(mins - 1);
+        }
+        Some(self.elements.remove(index))
+    }
+}
+
+impl<T> IntoIterator for MinStack<T> {
+    type Item = T;
+    type IntoIter = array_stack::IntoIter<T>;
+
+    /// Iterate over the elements, from bottom to top
+    fn into_iter(self) -> array_stack::IntoIter<T> {
+        self.elements.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a MinStack<T> {
+    type Item = &'a T;
+    type IntoIter = array_stack::Iter<'a, T>;
+
+    fn into_iter(self) -> array_stack::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Self::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl<T: Ord> Extend<T> for MinStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: Clone> Clone for MinStack<T> {
+    fn clone(&self) -> Self {
+        MinStack {
+            elements: self.elements.clone(),
+            mins: self.mins.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MinStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for MinStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+impl<T: Eq> Eq for MinStack<T> {}
+
+impl<T> Default for MinStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Stack for MinStack<T> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        MinStack::push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        MinStack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        MinStack::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MinStack;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::vec::Vec;
+
+    #[test]
+    fn should_report_the_minimum() {
+        let mut stack = MinStack::new();
+        assert_eq!(stack.min(), None);
+        stack.push(3);
+        stack.push(5);
+        stack.push(1);
+        stack.push(1);
+        stack.push(4);
+        assert_eq!(stack.min(), Some(&1));
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(1));
+        // The other 1 is still in the stack
+        assert_eq!(stack.min(), Some(&1));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.min(), Some(&3));
+        assert_eq!(stack.peek(), Some(&5));
+        assert_eq!(stack.pop(), Some(5));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.min(), None);
+    }
+
+    #[test]
+    fn should_behave_like_a_vec() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut stack = MinStack::new();
+        let mut vec = Vec::new();
+        for _ in 0..10_000 {
+            if rng.gen_bool(0.6) {
+                let element = rng.gen_range(0..1_000);
+                stack.push(element);
+                vec.push(element);
+            } else {
+                assert_eq!(stack.pop(), vec.pop());
+            }
+            assert_eq!(stack.min(), vec.iter().min());
+            assert_eq!(stack.peek(), vec.last());
+        }
+        assert!(stack.iter().eq(vec.iter()));
+        assert!(stack.into_iter().eq(vec));
+    }
+}
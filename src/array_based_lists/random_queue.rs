@@ -0,0 +1,351 @@
+//! # RandomQueue: Removing Uniformly Random Elements
+//!
+//! See Chapter 2, Exercise 2.2
+
+use crate::array_based_lists::array_stack::ArrayStack;
+use crate::error::{InsertError, OdsError};
+use crate::queue::Queue;
+use core::iter::FromIterator;
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hasher};
+
+/// A source of random indices
+///
+/// Implemented by `XorShift`, and by closures mapping a bound to an index.
+pub trait RandomSource {
+    /// A uniformly random index in `0..bound`, `bound` being positive
+    fn gen_index(&mut self, bound: usize) -> usize;
+}
+
+impl<F: FnMut(usize) -> usize> RandomSource for F {
+    fn gen_index(&mut self, bound: usize) -> usize {
+        self(bound)
+    }
+}
+
+/// A xorshift64* pseudo-random generator
+///
+/// Fast, but not suitable for cryptographic purposes.
+#[derive(Clone, Debug)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// Create a new generator from given seed
+    pub fn new(seed: u64) -> Self {
+        // The state must not be zero
+        let state = if seed == 0 {
+            0x9e37_79b9_7f4a_7c15
+        } else {
+            seed
+        };
+        XorShift { state }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for XorShift {
+    /// Create a new generator seeded from the random keys of the standard library hash maps
+    fn default() -> Self {
+        XorShift::new(RandomState::new().build_hasher().finish())
+    }
+}
+
+impl RandomSource for XorShift {
+    fn gen_index(&mut self, bound: usize) -> usize {
+        // Multiply and reject the biased low products (Lemire's method)
+        let bound = bound as u64;
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(bound);
+            if product as u64 >= threshold {
+                return (product >> 64) as usize;
+            }
+        }
+    }
+}
+
+/// RandomQueue: a queue removing uniformly random elements
+///
+/// The elements are stored in an `ArrayStack`. The next element to remove is drawn in advance
+/// and kept on top of the stack, so it can be peeked.
+pub struct RandomQueue<T, R = XorShift> {
+    stack: ArrayStack<T>,
+    source: R,
+}
+
+#[cfg(feature = "std")]
+impl<T> RandomQueue<T> {
+    /// Create a new RandomQueue, with a randomly seeded `XorShift` generator
+    ///
+    /// Requires the `std` feature, see `with_source` otherwise.
+    pub fn new() -> Self {
+        RandomQueue::with_source(XorShift::default())
+    }
+}
+
+impl<T, R: RandomSource> RandomQueue<T, R> {
+    /// Create a new RandomQueue drawing the removed elements from given source
+    pub fn with_source(source: R) -> Self {
+        RandomQueue {
+            stack: ArrayStack::new(),
+            source,
+        }
+    }
+
+    /// The random queue length
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Is the random queue empty ?
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Insert given element
+    pub fn add(&mut self, element: T) {
+        let len = self.stack.len();
+        self.stack.add(len, element);
+        self.draw();
+    }
+
+    /// Insert given element
+    ///
+    /// Hands the element back if the backing stack cannot grow.
+    pub fn try_add(&mut self, element: T) -> Result<(), InsertError<T>> {
+        self.stack.try_push(element)?;
+        self.draw();
+        Ok(())
+    }
+
+    /// Remove and return a uniformly random element
+    ///
+    /// Fails if the queue is empty
+    pub fn try_remove(&mut self) -> Result<T, OdsError> {
+        self.remove().ok_or(OdsError::Empty)
+    }
+
+    /// Make room for at least `additional` more elements
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), OdsError> {
+        self.stack.try_reserve(additional)
+    }
+
+    /// Remove and return a uniformly random element
+    pub fn remove(&mut self) -> Option<T> {
+        let len = self.stack.len();
+        if len > 0 {
+            let element = self.stack.remove(len - 1);
+            self.draw();
+            Some(element)
+        } else {
+            None
+        }
+    }
+
+    /// Get a reference to the next element to be removed
+    pub fn peek(&self) -> Option<&T> {
+        let len = self.stack.len();
+        len.checked_sub(1).and_then(|index| self.stack.get(index))
+    }
+
+    /// Drop all the elements, keeping the allocated capacity
+    pub fn clear(&mut self) {
+        self.stack.clear();
+    }
+
+    /// Draw the next element to remove among all the elements, and move it on top of the stack
+    fn draw(&mut self) {
+        let len = self.stack.len();
+        if len > 1 {
+            let index = self.source.gen_index(len);
+            self.stack.swap(index, len - 1);
+        }
+    }
+}
+
+impl<T, R: RandomSource + Default> Default for RandomQueue<T, R> {
+    fn default() -> Self {
+        RandomQueue::with_source(R::default())
+    }
+}
+
+impl<T, R: RandomSource + Default> FromIterator<T> for RandomQueue<T, R> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = RandomQueue::default();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T, R: RandomSource> Extend<T> for RandomQueue<T, R> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.stack.extend(iter);
+        self.draw();
+    }
+}
+
+impl<T, R: RandomSource> Queue for RandomQueue<T, R> {
+    type Item = T;
+
+    fn add(&mut self, item: T) {
+        RandomQueue::add(self, item)
+    }
+
+    fn remove(&mut self) -> Option<T> {
+        RandomQueue::remove(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        RandomQueue::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        self.stack.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RandomQueue, RandomSource, XorShift};
+    use crate::error::OdsError;
+    use crate::queue::Queue;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::vec::Vec;
+
+    /// Pearson's chi-squared statistic of `counts` against a uniform distribution
+    fn chi_squared(counts: &[usize]) -> f64 {
+        let total: usize = counts.iter().sum();
+        let expected = total as f64 / counts.len() as f64;
+        counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn should_remove_all_elements() {
+        let mut queue = RandomQueue::new();
+        for i in 0..100 {
+            queue.add(i);
+        }
+        assert_eq!(queue.len(), 100);
+        let mut removed: Vec<_> = std::iter::from_fn(|| queue.remove()).collect();
+        removed.sort_unstable();
+        assert!(removed.into_iter().eq(0..100));
+        assert!(queue.is_empty());
+        assert_eq!(queue.remove(), None);
+    }
+
+    #[test]
+    fn should_use_given_source() {
+        // Always pick the first element, which is then replaced by the last one
+        let mut queue = RandomQueue::with_source(|_| 0);
+        queue.extend(0..5);
+        let removed: Vec<_> = std::iter::from_fn(|| Queue::remove(&mut queue)).collect();
+        assert_eq!(removed, [0, 4, 3, 2, 1]);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut queue = RandomQueue::with_source(move |bound| rng.gen_range(0..bound));
+        queue.extend(0..5);
+        let mut removed: Vec<_> = std::iter::from_fn(|| queue.remove()).collect();
+        removed.sort_unstable();
+        assert_eq!(removed, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn should_try_to_add_elements() {
+        let mut queue = RandomQueue::with_source(|_| 0);
+        queue.try_reserve(3).unwrap();
+        for i in 0..3 {
+            queue.try_add(i).unwrap();
+        }
+        let next = queue.peek().copied();
+        assert_eq!(queue.remove(), next);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(
+            queue.try_reserve(usize::MAX),
+            Err(OdsError::capacity_overflow::<i32>())
+        );
+        while queue.try_remove().is_ok() {}
+        assert_eq!(queue.try_remove(), Err(OdsError::Empty));
+    }
+
+    #[test]
+    fn should_peek_the_next_removed_element() {
+        let mut queue = RandomQueue::with_source(XorShift::new(42));
+        assert_eq!(queue.peek(), None);
+        queue.extend(0..50);
+        for i in 50..100 {
+            queue.add(i);
+        }
+        while let Some(&next) = queue.peek() {
+            assert_eq!(queue.remove(), Some(next));
+        }
+
+        // A source borrowing its generator cannot be cloned
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut queue = RandomQueue::with_source(|bound| rng.gen_range(0..bound));
+        queue.extend(0..100);
+        while let Some(&next) = Queue::peek(&queue) {
+            assert_eq!(Queue::remove(&mut queue), Some(next));
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn should_generate_indices_in_bounds() {
+        let mut source = XorShift::new(42);
+        for bound in (1..100).chain([usize::MAX, usize::MAX / 3 * 2]) {
+            for _ in 0..100 {
+                assert!(source.gen_index(bound) < bound);
+            }
+        }
+    }
+
+    #[test]
+    fn should_remove_elements_uniformly() {
+        const LEN: usize = 10;
+        const DRAWS: usize = 20_000;
+        // Chi-squared critical value for 9 degrees of freedom at p = 0.001
+        const CRITICAL: f64 = 27.877;
+
+        let mut queue = RandomQueue::with_source(XorShift::new(7));
+        let mut first = [0; LEN];
+        let mut last = [0; LEN];
+        for _ in 0..DRAWS {
+            queue.extend(0..LEN);
+            first[queue.remove().unwrap()] += 1;
+            for _ in 1..LEN - 1 {
+                queue.remove();
+            }
+            last[queue.remove().unwrap()] += 1;
+        }
+        assert!(chi_squared(&first) < CRITICAL, "{:?}", first);
+        assert!(chi_squared(&last) < CRITICAL, "{:?}", last);
+    }
+
+    #[test]
+    fn should_detect_biased_sources() {
+        let mut counts = [0; 10];
+        let mut source = XorShift::new(7);
+        for _ in 0..20_000 {
+            // Favour the first index
+            let index = source.gen_index(11) % 10;
+            counts[index] += 1;
+        }
+        assert!(chi_squared(&counts) > 27.877, "{:?}", counts);
+    }
+}
@@ -0,0 +1,131 @@
+//! Raw storage backing the array-based lists
+//!
+//! `RawBuf` owns the slots and the resize policy, while the lists keep track of
+//! which slots are initialized.
+
+use crate::array_based_lists::resize_policy::ResizePolicy;
+use crate::array_based_lists::ring;
+use crate::error::OdsError;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::alloc::Layout;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+
+/// A fixed number of possibly uninitialized slots, along with the policy used to resize them
+pub(crate) struct RawBuf<T> {
+    slots: Box<[MaybeUninit<T>]>,
+    policy: ResizePolicy,
+}
+
+impl<T> RawBuf<T> {
+    /// An empty buffer, without allocation
+    pub(crate) fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// A buffer of `capacity` uninitialized slots
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        RawBuf {
+            slots: uninit_slots(capacity),
+            policy: ResizePolicy::default(),
+        }
+    }
+
+    /// A buffer of `capacity` uninitialized slots, reporting allocation failures
+    pub(crate) fn try_with_capacity(capacity: usize) -> Result<Self, OdsError> {
+        Ok(RawBuf {
+            slots: try_uninit_slots(capacity)?,
+            policy: ResizePolicy::default(),
+        })
+    }
+
+    /// The number of slots
+    pub(crate) fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub(crate) fn policy(&self) -> ResizePolicy {
+        self.policy
+    }
+
+    pub(crate) fn set_policy(&mut self, policy: ResizePolicy) {
+        self.policy = policy;
+    }
+
+    /// Move the `len` elements of the ring starting at `offset` to the beginning of
+    /// `capacity` new slots
+    ///
+    /// # Safety
+    /// The `len` slots of the ring starting at `offset` must be initialized,
+    /// and `capacity` must be at least `len`.
+    pub(crate) unsafe fn reallocate(&mut self, offset: usize, len: usize, capacity: usize) {
+        let mut slots = uninit_slots(capacity);
+        ring::copy_to_contiguous(
+            self.slots.as_ptr(),
+            self.slots.len(),
+            offset,
+            len,
+            slots.as_mut_ptr(),
+        );
+        self.slots = slots;
+    }
+
+    /// Move the `len` elements of the ring starting at `offset` to the beginning of
+    /// `capacity` new slots, keeping the current slots if they cannot be allocated
+    ///
+    /// # Safety
+    /// The `len` slots of the ring starting at `offset` must be initialized,
+    /// and `capacity` must be at least `len`.
+    pub(crate) unsafe fn try_reallocate(
+        &mut self,
+        offset: usize,
+        len: usize,
+        capacity: usize,
+    ) -> Result<(), OdsError> {
+        let mut slots = try_uninit_slots(capacity)?;
+        ring::copy_to_contiguous(
+            self.slots.as_ptr(),
+            self.slots.len(),
+            offset,
+            len,
+            slots.as_mut_ptr(),
+        );
+        self.slots = slots;
+        Ok(())
+    }
+}
+
+impl<T> Deref for RawBuf<T> {
+    type Target = [MaybeUninit<T>];
+
+    fn deref(&self) -> &[MaybeUninit<T>] {
+        &self.slots
+    }
+}
+
+impl<T> DerefMut for RawBuf<T> {
+    fn deref_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        &mut self.slots
+    }
+}
+
+/// Allocate `capacity` uninitialized slots
+pub(crate) fn uninit_slots<T>(capacity: usize) -> Box<[MaybeUninit<T>]> {
+    let mut slots = Vec::with_capacity(capacity);
+    slots.resize_with(capacity, MaybeUninit::uninit);
+    slots.into_boxed_slice()
+}
+
+/// Allocate `capacity` uninitialized slots, reporting allocation failures
+pub(crate) fn try_uninit_slots<T>(capacity: usize) -> Result<Box<[MaybeUninit<T>]>, OdsError> {
+    if Layout::array::<T>(capacity).is_err() {
+        return Err(OdsError::capacity_overflow::<T>());
+    }
+    let mut slots = Vec::new();
+    slots
+        .try_reserve_exact(capacity)
+        .map_err(|_| OdsError::AllocationFailed)?;
+    slots.resize_with(capacity, MaybeUninit::uninit);
+    Ok(slots.into_boxed_slice())
+}
@@ -0,0 +1,132 @@
+//! # Resize policy of the array-based lists
+//!
+//! See Chapter 2.1.2: the backing array is grown to `2n` slots when it is full, and shrunk
+//! to `2n` slots when it holds `3n` slots or more, which gives amortized O(1) resizes.
+
+/// When and how much the backing array of a list is grown or shrunk
+///
+/// The backing array is grown to `growth_factor * n` slots when it is full and, unless shrinking
+/// is disabled, shrunk to `growth_factor * n` slots once its capacity reaches
+/// `shrink_threshold * n`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ResizePolicy {
+    growth_factor: usize,
+    shrink_threshold: Option<usize>,
+}
+
+impl ResizePolicy {
+    /// A policy growing by `growth_factor`, and shrinking from `shrink_threshold` if any
+    ///
+    /// # Panics
+    /// Panics if `growth_factor` < 2 or if `shrink_threshold` is not greater than `growth_factor`
+    pub fn new(growth_factor: usize, shrink_threshold: Option<usize>) -> Self {
+        if growth_factor < 2 {
+            panic!("growth factor (is {}) should be >= 2", growth_factor);
+        }
+        if let Some(shrink_threshold) = shrink_threshold {
+            // Shrinking right after growing would break the amortized bounds
+            if shrink_threshold <= growth_factor {
+                panic!(
+                    "shrink threshold (is {}) should be > growth factor (is {})",
+                    shrink_threshold, growth_factor
+                );
+            }
+        }
+        ResizePolicy {
+            growth_factor,
+            shrink_threshold,
+        }
+    }
+
+    /// Never shrink the backing array automatically
+    ///
+    /// Capacity is then only released by `shrink_to_fit`.
+    pub fn without_shrinking(self) -> Self {
+        ResizePolicy {
+            shrink_threshold: None,
+            ..self
+        }
+    }
+
+    /// The factor the length is multiplied by on resize
+    pub fn growth_factor(&self) -> usize {
+        self.growth_factor
+    }
+
+    /// The ratio of capacity to length from which the backing array is shrunk, if any
+    pub fn shrink_threshold(&self) -> Option<usize> {
+        self.shrink_threshold
+    }
+
+    /// The capacity to grow a full backing array of `len` elements to
+    ///
+    /// # Panics
+    /// Panics if the capacity overflows `usize`
+    #[inline]
+    pub(crate) fn grown_capacity(&self, len: usize) -> usize {
+        self.try_grown_capacity(len).expect("capacity overflow")
+    }
+
+    /// The capacity to grow a full backing array of `len` elements to, if it fits in `usize`
+    #[inline]
+    pub(crate) fn try_grown_capacity(&self, len: usize) -> Option<usize> {
+        let capacity = len.checked_mul(self.growth_factor)?;
+        Some(usize::max(1, capacity))
+    }
+
+    /// The capacity to shrink a backing array of `len` elements to, if it is too sparse
+    #[inline]
+    pub(crate) fn shrunk_capacity(&self, capacity: usize, len: usize) -> Option<usize> {
+        let shrink_threshold = self.shrink_threshold?;
+        if capacity >= len.saturating_mul(shrink_threshold) {
+            let shrunk = usize::max(1, len * self.growth_factor);
+            if shrunk < capacity {
+                return Some(shrunk);
+            }
+        }
+        None
+    }
+}
+
+impl Default for ResizePolicy {
+    /// The policy from the book: grow by a factor 2, shrink at 3 times the length
+    fn default() -> Self {
+        ResizePolicy {
+            growth_factor: 2,
+            shrink_threshold: Some(3),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResizePolicy;
+
+    #[test]
+    fn should_follow_the_book_by_default() {
+        let policy = ResizePolicy::default();
+        assert_eq!(policy.grown_capacity(0), 1);
+        assert_eq!(policy.grown_capacity(4), 8);
+        assert_eq!(policy.shrunk_capacity(8, 3), None);
+        assert_eq!(policy.shrunk_capacity(9, 3), Some(6));
+        assert_eq!(policy.shrunk_capacity(1, 0), None);
+    }
+
+    #[test]
+    fn should_be_configurable() {
+        let policy = ResizePolicy::new(4, Some(8));
+        assert_eq!(policy.grown_capacity(4), 16);
+        assert_eq!(policy.shrunk_capacity(16, 2), Some(8));
+        assert_eq!(policy.shrunk_capacity(16, 3), None);
+
+        let policy = policy.without_shrinking();
+        assert_eq!(policy.shrink_threshold(), None);
+        assert_eq!(policy.shrunk_capacity(1024, 0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_reject_thrashing_policies() {
+        ResizePolicy::new(3, Some(3));
+    }
+}
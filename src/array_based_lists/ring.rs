@@ -0,0 +1,603 @@
+//! Bulk moves inside a ring buffer
+//!
+//! The elements of a ring are stored in at most two contiguous segments of the buffer, so they
+//! can be moved with a few `ptr::copy` instead of one read/write per element
+//! (see FastArrayStack, Chapter 2.1.2). The iterators over those segments are shared as well.
+//!
+//! `ArrayQueue` and `ArrayDeque` keep their capacities at powers of two, so logical indices are
+//! mapped to positions in the buffer with a mask instead of an integer division. `RingBuffer`,
+//! `InlineArrayQueue` and the blocks of `SEList` have arbitrary capacities.
+//!
+//! `wrap`, the shifts, `copy_to_contiguous`, `as_slices`, `make_contiguous`, `Iter`, `IterMut`
+//! and `IntoIter` work with any capacity; `mask`, `rotate` and `Drain` require a power of two.
+
+use crate::array_based_lists::raw_buf::RawBuf;
+use core::iter::FusedIterator;
+use core::mem::{self, MaybeUninit};
+use core::ops::Range;
+use core::{ptr, slice};
+
+/// The power of two capacity used for a ring requested to hold `capacity` elements
+///
+/// # Panics
+/// Panics if the capacity overflows `usize`
+pub(crate) fn capacity(capacity: usize) -> usize {
+    try_capacity(capacity).expect("capacity overflow")
+}
+
+/// The power of two capacity used for a ring requested to hold `capacity` elements,
+/// if it fits in `usize`
+pub(crate) fn try_capacity(capacity: usize) -> Option<usize> {
+    if capacity == 0 {
+        Some(0)
+    } else {
+        capacity.checked_next_power_of_two()
+    }
+}
+
+/// Map a position into a buffer of power of two `capacity`
+#[inline]
+pub(crate) fn mask(position: usize, capacity: usize) -> usize {
+    debug_assert!(capacity.is_power_of_two());
+    position & (capacity - 1)
+}
+
+/// Wrap a position lower than `2 * capacity` into the buffer
+#[inline]
+pub(crate) fn wrap(position: usize, capacity: usize) -> usize {
+    if position >= capacity {
+        position - capacity
+    } else {
+        position
+    }
+}
+
+/// Move the `count` elements starting at physical position `start` `distance` slots to the right
+///
+/// # Safety
+/// `buf` must point to `capacity` slots, `start` must be lower than `capacity`
+/// and `count + distance` must not exceed `capacity`.
+pub(crate) unsafe fn shift_right<T>(
+    buf: *mut MaybeUninit<T>,
+    capacity: usize,
+    start: usize,
+    count: usize,
+    distance: usize,
+) {
+    // Move contiguous chunks from the end, so sources are read before being overwritten
+    let mut remaining = count;
+    while remaining > 0 {
+        let src_last = wrap(start + remaining - 1, capacity);
+        let dst_last = wrap(start + remaining - 1 + distance, capacity);
+        let chunk = usize::min(remaining, usize::min(src_last, dst_last) + 1);
+        ptr::copy(
+            buf.add(src_last + 1 - chunk),
+            buf.add(dst_last + 1 - chunk),
+            chunk,
+        );
+        remaining -= chunk;
+    }
+}
+
+/// Move the `count` elements starting at physical position `start` `distance` slots to the left
+///
+/// # Safety
+/// `buf` must point to `capacity` slots, `start` must be lower than `capacity`
+/// and `count + distance` must not exceed `capacity`.
+pub(crate) unsafe fn shift_left<T>(
+    buf: *mut MaybeUninit<T>,
+    capacity: usize,
+    start: usize,
+    count: usize,
+    distance: usize,
+) {
+    // Move contiguous chunks from the start, so sources are read before being overwritten
+    let mut moved = 0;
+    while moved < count {
+        let src = wrap(start + moved, capacity);
+        let dst = wrap(src + capacity - distance, capacity);
+        let chunk = usize::min(count - moved, capacity - usize::max(src, dst));
+        ptr::copy(buf.add(src), buf.add(dst), chunk);
+        moved += chunk;
+    }
+}
+
+/// Copy the `len` elements of the ring starting at physical position `offset`
+/// to the beginning of `dst`
+///
+/// # Safety
+/// `src` must point to `capacity` slots holding the ring, `dst` must point to at least `len`
+/// slots and not overlap `src`.
+pub(crate) unsafe fn copy_to_contiguous<T>(
+    src: *const MaybeUninit<T>,
+    capacity: usize,
+    offset: usize,
+    len: usize,
+    dst: *mut MaybeUninit<T>,
+) {
+    if offset + len > capacity {
+        // Copy offset..capacity
+        ptr::copy_nonoverlapping(src.add(offset), dst, capacity - offset);
+        // Copy 0..(offset + len - capacity)
+        ptr::copy_nonoverlapping(src, dst.add(capacity - offset), offset + len - capacity);
+    } else {
+        // Copy offset..offset + len
+        ptr::copy_nonoverlapping(src.add(offset), dst, len);
+    }
+}
+
+/// The two contiguous segments holding the `len` elements of the ring starting at `offset`
+///
+/// # Safety
+/// The `len` slots of the ring starting at `offset` must be initialized.
+pub(crate) unsafe fn as_slices<T>(
+    buf: &[MaybeUninit<T>],
+    offset: usize,
+    len: usize,
+) -> (&[T], &[T]) {
+    let capacity = buf.len();
+    let p = buf.as_ptr() as *const T;
+    if offset + len > capacity {
+        (
+            slice::from_raw_parts(p.add(offset), capacity - offset),
+            slice::from_raw_parts(p, offset + len - capacity),
+        )
+    } else {
+        (slice::from_raw_parts(p.add(offset), len), &[])
+    }
+}
+
+/// The two contiguous segments holding the `len` elements of the ring starting at `offset`
+///
+/// # Safety
+/// The `len` slots of the ring starting at `offset` must be initialized.
+pub(crate) unsafe fn as_mut_slices<T>(
+    buf: &mut [MaybeUninit<T>],
+    offset: usize,
+    len: usize,
+) -> (&mut [T], &mut [T]) {
+    let capacity = buf.len();
+    let p = buf.as_mut_ptr() as *mut T;
+    if offset + len > capacity {
+        (
+            slice::from_raw_parts_mut(p.add(offset), capacity - offset),
+            slice::from_raw_parts_mut(p, offset + len - capacity),
+        )
+    } else {
+        (slice::from_raw_parts_mut(p.add(offset), len), &mut [])
+    }
+}
+
+/// Rotate the `len` elements of the ring starting at `offset`, so that the element at index `i`
+/// moves to index `(i + r) % len`, and return the new offset
+///
+/// Moves `min(r, len - r)` elements, or none if the ring is full.
+/// The capacity of the ring must be a power of two.
+pub(crate) fn rotate<T>(buf: &mut [MaybeUninit<T>], offset: usize, len: usize, r: usize) -> usize {
+    if len == 0 {
+        return offset;
+    }
+    let capacity = buf.len();
+    debug_assert!(capacity.is_power_of_two());
+    let r = r % len;
+    if len == capacity {
+        return mask(offset + capacity - r, capacity);
+    }
+
+    let mut offset = offset;
+    if r <= len - r {
+        // move the last r elements in front of the first one
+        for _ in 0..r {
+            let back = mask(offset + len - 1, capacity);
+            offset = mask(offset + capacity - 1, capacity);
+            buf.swap(back, offset);
+        }
+    } else {
+        // move the first len - r elements after the last one
+        for _ in 0..len - r {
+            buf.swap(offset, mask(offset + len, capacity));
+            offset = mask(offset + 1, capacity);
+        }
+    }
+    offset
+}
+
+/// Move the `len` elements of the ring starting at `offset` into a single segment,
+/// and return the new offset
+pub(crate) fn make_contiguous<T>(buf: &mut [MaybeUninit<T>], offset: usize, len: usize) -> usize {
+    if offset + len > buf.len() {
+        buf.rotate_left(offset);
+        0
+    } else {
+        offset
+    }
+}
+
+/// An iterator over references to the elements of a ring, from front to back
+pub struct Iter<'a, T> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new((front, back): (&'a [T], &'a [T])) -> Self {
+        Iter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over mutable references to the elements of a ring, from front to back
+pub struct IterMut<'a, T> {
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new((front, back): (&'a mut [T], &'a mut [T])) -> Self {
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a ring, from front to back
+pub struct IntoIter<T> {
+    buf: RawBuf<T>,
+    offset: usize,
+    len: usize,
+}
+
+impl<T> IntoIter<T> {
+    /// Take ownership of the `len` elements of the ring starting at `offset`
+    pub(crate) fn new(buf: RawBuf<T>, offset: usize, len: usize) -> Self {
+        IntoIter { buf, offset, len }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let element = unsafe { self.buf[self.offset].as_ptr().read() };
+        self.offset = wrap(self.offset + 1, self.buf.len());
+        self.len -= 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let position = wrap(self.offset + self.len, self.buf.len());
+        Some(unsafe { self.buf[position].as_ptr().read() })
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        let len = mem::replace(&mut self.len, 0);
+        unsafe {
+            let (front, back) = as_mut_slices(&mut self.buf, self.offset, len);
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+/// A draining iterator over a range of the elements of a ring
+///
+/// The remaining elements are dropped and the gap is closed, moving the shorter side,
+/// when the iterator is dropped.
+pub struct Drain<'a, T> {
+    buf: &'a mut [MaybeUninit<T>],
+    offset: &'a mut usize,
+    len: &'a mut usize,
+    /// The drained range, relative to the front of the ring
+    range: Range<usize>,
+    /// The elements of `range` that were not yielded yet
+    remaining: Range<usize>,
+    /// The length of the ring before draining
+    old_len: usize,
+}
+
+impl<'a, T> Drain<'a, T> {
+    /// Drain given `range` of the `len` elements of the ring starting at `offset`
+    ///
+    /// `range` must be included in `0..len`, and the capacity of the ring must be a power of two.
+    pub(crate) fn new(
+        buf: &'a mut [MaybeUninit<T>],
+        offset: &'a mut usize,
+        len: &'a mut usize,
+        range: Range<usize>,
+    ) -> Self {
+        debug_assert!(buf.is_empty() || buf.len().is_power_of_two());
+        // Only keep the head while draining, so leaking the iterator leaks the tail
+        let old_len = mem::replace(len, range.start);
+        Drain {
+            buf,
+            offset,
+            len,
+            remaining: range.clone(),
+            range,
+            old_len,
+        }
+    }
+
+    fn read(&mut self, index: usize) -> T {
+        let position = mask(*self.offset + index, self.buf.len());
+        unsafe { self.buf[position].as_ptr().read() }
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let index = self.remaining.next()?;
+        Some(self.read(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        let index = self.remaining.next_back()?;
+        Some(self.read(index))
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+
+        let capacity = self.buf.len();
+        let head = self.range.start;
+        let tail = self.old_len - self.range.end;
+        let gap = self.range.len();
+        let p = self.buf.as_mut_ptr();
+        if gap > 0 {
+            if head < tail {
+                unsafe { shift_right(p, capacity, *self.offset, head, gap) };
+                *self.offset = wrap(*self.offset + gap, capacity);
+            } else {
+                let start = wrap(*self.offset + self.range.end, capacity);
+                unsafe { shift_left(p, capacity, start, tail, gap) };
+            }
+        }
+        *self.len = head + tail;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        as_mut_slices, as_slices, capacity, make_contiguous, mask, rotate, shift_left, shift_right,
+        Drain, IntoIter, Iter, IterMut,
+    };
+    use crate::array_based_lists::raw_buf::RawBuf;
+    use std::mem::MaybeUninit;
+    use std::ptr;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    fn ring(capacity: usize) -> Vec<MaybeUninit<usize>> {
+        (0..capacity).map(MaybeUninit::new).collect()
+    }
+
+    fn read(buf: &[MaybeUninit<usize>], position: usize) -> usize {
+        unsafe { buf[position % buf.len()].assume_init() }
+    }
+
+    #[test]
+    fn should_round_capacities_to_powers_of_two() {
+        assert_eq!(capacity(0), 0);
+        assert_eq!(capacity(1), 1);
+        assert_eq!(capacity(5), 8);
+        assert_eq!(capacity(8), 8);
+        assert_eq!(mask(13, 8), 5);
+        assert_eq!(mask(7, 8), 7);
+    }
+
+    #[test]
+    fn should_shift_right_across_the_boundary() {
+        const CAPACITY: usize = 7;
+        for start in 0..CAPACITY {
+            for distance in 1..CAPACITY {
+                for count in 0..=CAPACITY - distance {
+                    let mut buf = ring(CAPACITY);
+                    unsafe { shift_right(buf.as_mut_ptr(), CAPACITY, start, count, distance) };
+                    for k in 0..count {
+                        assert_eq!(read(&buf, start + k + distance), (start + k) % CAPACITY);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_shift_left_across_the_boundary() {
+        const CAPACITY: usize = 7;
+        for start in 0..CAPACITY {
+            for distance in 1..CAPACITY {
+                for count in 0..=CAPACITY - distance {
+                    let mut buf = ring(CAPACITY);
+                    unsafe { shift_left(buf.as_mut_ptr(), CAPACITY, start, count, distance) };
+                    for k in 0..count {
+                        let position = start + k + CAPACITY - distance;
+                        assert_eq!(read(&buf, position), (start + k) % CAPACITY);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_rotate_across_the_boundary() {
+        const CAPACITY: usize = 8;
+        for offset in 0..CAPACITY {
+            for len in 0..=CAPACITY {
+                for r in 0..2 * CAPACITY {
+                    let mut buf = ring(CAPACITY);
+                    let new_offset = rotate(&mut buf, offset, len, r);
+                    for i in 0..len {
+                        assert_eq!(
+                            read(&buf, new_offset + (i + r) % len),
+                            (offset + i) % CAPACITY
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_make_rings_contiguous() {
+        const CAPACITY: usize = 8;
+        for offset in 0..CAPACITY {
+            for len in 0..=CAPACITY {
+                let mut buf = ring(CAPACITY);
+                let new_offset = make_contiguous(&mut buf, offset, len);
+                assert!(new_offset + len <= CAPACITY);
+                for i in 0..len {
+                    assert_eq!(read(&buf, new_offset + i), (offset + i) % CAPACITY);
+                }
+            }
+        }
+    }
+
+    /// A ring of 16 slots holding `len` clones of `watcher` from position 12, wrapping around
+    fn watched_ring(watcher: &Rc<()>, len: usize) -> RawBuf<Rc<()>> {
+        let mut buf = RawBuf::with_capacity(16);
+        for k in 0..len {
+            buf[mask(12 + k, 16)] = MaybeUninit::new(Rc::clone(watcher));
+        }
+        buf
+    }
+
+    #[test]
+    fn should_iterate_across_the_boundary() {
+        let mut buf = ring(16);
+        let expected: Vec<_> = (12..16).chain(0..4).collect();
+        let slices = unsafe { as_slices(&buf, 12, 8) };
+        assert!(Iter::new(slices).eq(expected.iter()));
+        assert!(Iter::new(slices).rev().eq(expected.iter().rev()));
+        assert_eq!(Iter::new(slices).len(), 8);
+        let slices = unsafe { as_mut_slices(&mut buf, 12, 8) };
+        for element in IterMut::new(slices).rev().take(5) {
+            *element += 100;
+        }
+        let (front, back) = unsafe { as_slices(&buf, 12, 8) };
+        assert_eq!(front, [12, 13, 14, 115]);
+        assert_eq!(back, [100, 101, 102, 103]);
+    }
+
+    #[test]
+    fn should_drop_remaining_elements_of_into_iter() {
+        let watcher = Rc::new(());
+        let mut iter = IntoIter::new(watched_ring(&watcher, 8), 12, 8);
+        assert_eq!(Rc::strong_count(&watcher), 9);
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 6);
+        assert_eq!(Rc::strong_count(&watcher), 7);
+        drop(iter);
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+
+    #[test]
+    fn should_drop_drained_elements() {
+        let watcher = Rc::new(());
+        let mut buf = watched_ring(&watcher, 10);
+        let (mut offset, mut len) = (12, 10);
+        let mut drain = Drain::new(&mut buf, &mut offset, &mut len, 3..8);
+        drain.next();
+        assert_eq!(Rc::strong_count(&watcher), 10);
+        drop(drain);
+        assert_eq!(Rc::strong_count(&watcher), 6);
+        assert_eq!(len, 5);
+        unsafe {
+            let (front, back) = as_mut_slices(&mut buf, offset, len);
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+}
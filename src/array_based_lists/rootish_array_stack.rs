@@ -0,0 +1,504 @@
+//! # RootishArrayStack: A Space-Efficient Array Stack
+//!
+//! See Chapter 2.6
+
+use crate::array_based_lists::array_stack::ArrayStack;
+use crate::array_based_lists::raw_buf::{try_uninit_slots, uninit_slots};
+use crate::error::{InsertError, OdsError};
+use crate::list::List;
+use crate::stack::Stack;
+use alloc::boxed::Box;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+/// RootishArrayStack: A Space-Efficient Array Stack
+///
+/// Elements are stored in a sequence of blocks of size 1, 2, 3, ...
+/// so at most O(sqrt(n)) slots are wasted and existing elements are never copied on growth.
+pub struct RootishArrayStack<T> {
+    blocks: ArrayStack<Box<[MaybeUninit<T>]>>,
+    len: usize,
+}
+
+impl<T> RootishArrayStack<T> {
+    /// Create a new RootishArrayStack
+    pub fn new() -> Self {
+        Self {
+            blocks: ArrayStack::new(),
+            len: 0,
+        }
+    }
+
+    /// The rootish array stack length
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the rootish array stack empty ?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a reference to element at given index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            Some(unsafe { &*self.slot(index) })
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to element at given index
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            Some(unsafe { &mut *self.slot_mut(index) })
+        } else {
+            None
+        }
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// # Panics
+    /// Panics if `index` > `len`
+    pub fn add(&mut self, index: usize, element: T) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+        let len = self.len;
+        if index > len {
+            assert_failed(index, len);
+        }
+
+        let blocks = self.blocks.len();
+        if blocks * (blocks + 1) / 2 < len + 1 {
+            self.grow();
+        }
+
+        unsafe {
+            for k in (index..len).rev() {
+                self.slot_mut(k + 1).write(self.slot_mut(k).read());
+            }
+            self.slot_mut(index).write(element);
+        }
+        self.len += 1;
+    }
+
+    /// Remove at returns element at given index, shifting all following elements to the left
+    ///
+    /// # Panics
+    /// Panics if `index` >= `len`
+    pub fn remove(&mut self, index: usize) -> T {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("removal index (is {}) should be < len (is {})", index, len);
+        }
+        let len = self.len;
+        if index >= len {
+            assert_failed(index, len);
+        }
+
+        let element;
+        unsafe {
+            element = self.slot_mut(index).read();
+            for k in index..len - 1 {
+                self.slot_mut(k).write(self.slot_mut(k + 1).read());
+            }
+        }
+        self.len -= 1;
+        self.shrink();
+        element
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// Hands the element back if `index` > `len` or if a new block cannot be allocated.
+    pub fn try_add(&mut self, index: usize, element: T) -> Result<(), InsertError<T>> {
+        let len = self.len;
+        if index > len {
+            let error = OdsError::IndexOutOfBounds { index, len };
+            return Err(InsertError::new(error, element));
+        }
+        if let Err(error) = self.try_reserve(1) {
+            return Err(InsertError::new(error, element));
+        }
+        self.add(index, element);
+        Ok(())
+    }
+
+    /// Remove and return the element at given index, shifting all following elements to the left
+    ///
+    /// Fails if `index` >= `len`
+    pub fn try_remove(&mut self, index: usize) -> Result<T, OdsError> {
+        let len = self.len;
+        if index < len {
+            Ok(self.remove(index))
+        } else {
+            Err(OdsError::IndexOutOfBounds { index, len })
+        }
+    }
+
+    /// Insert given element on top of the stack
+    ///
+    /// Hands the element back if a new block cannot be allocated.
+    pub fn try_push(&mut self, element: T) -> Result<(), InsertError<T>> {
+        self.try_add(self.len, element)
+    }
+
+    /// Allocate the blocks needed to hold at least `additional` more elements
+    ///
+    /// The spare blocks are released again by the next removal.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), OdsError> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .filter(|&required| required <= isize::MAX as usize)
+            .ok_or_else(OdsError::capacity_overflow::<T>)?;
+        if required == 0 {
+            return Ok(());
+        }
+        let blocks = i2b(required - 1) + 1;
+        let allocated = self.blocks.len();
+        if blocks > allocated {
+            self.blocks.try_reserve(blocks - allocated)?;
+            for size in allocated + 1..=blocks {
+                self.blocks.push(try_uninit_slots(size)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop all the elements and release their blocks
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keep the first `len` elements, dropping the others and releasing their blocks
+    ///
+    /// Has no effect if `len` is greater than the rootish array stack length.
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len;
+        if len >= old_len {
+            return;
+        }
+        self.len = len;
+        for index in len..old_len {
+            unsafe { ptr::drop_in_place(self.slot_mut(index)) };
+        }
+        self.shrink();
+    }
+
+    /// Pointer to the slot holding the element at given index
+    ///
+    /// `index` must be lower than the total capacity of the blocks.
+    fn slot(&self, index: usize) -> *const T {
+        let block = i2b(index);
+        let offset = index - block * (block + 1) / 2;
+        let block = self.blocks.get(block).expect("block should be allocated");
+        block[offset].as_ptr()
+    }
+
+    /// Mutable pointer to the slot holding the element at given index
+    ///
+    /// `index` must be lower than the total capacity of the blocks.
+    fn slot_mut(&mut self, index: usize) -> *mut T {
+        let block = i2b(index);
+        let offset = index - block * (block + 1) / 2;
+        let block = self
+            .blocks
+            .get_mut(block)
+            .expect("block should be allocated");
+        block[offset].as_mut_ptr()
+    }
+
+    /// Allocate a new block, one slot larger than the last one
+    fn grow(&mut self) {
+        let size = self.blocks.len() + 1;
+        self.blocks.push(uninit_slots(size));
+    }
+
+    /// Release the blocks that are not needed anymore, keeping at most one empty block
+    fn shrink(&mut self) {
+        let mut blocks = self.blocks.len();
+        while blocks > 0 && blocks.saturating_sub(2) * (blocks - 1) / 2 >= self.len {
+            blocks -= 1;
+        }
+        self.blocks.truncate(blocks);
+    }
+}
+
+/// Index of the block holding the element at given index
+///
+/// Block `b` holds indices `b(b+1)/2..(b+1)(b+2)/2`, so `b` is either
+/// `isqrt(2 * index)` or the block right before it.
+fn i2b(index: usize) -> usize {
+    let block = isqrt(2 * index);
+    if block * (block + 1) / 2 <= index {
+        block
+    } else {
+        block - 1
+    }
+}
+
+/// Integer square root, rounded down
+fn isqrt(n: usize) -> usize {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method, starting above the root so it decreases monotonically
+    let mut x = 1 << (usize::BITS - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+impl<T> Default for RootishArrayStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for RootishArrayStack<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Stack for RootishArrayStack<T> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        self.add(self.len, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let len = self.len;
+        if len > 0 {
+            Some(self.remove(len - 1))
+        } else {
+            None
+        }
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T> List for RootishArrayStack<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        RootishArrayStack::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        RootishArrayStack::get_mut(self, index)
+    }
+
+    fn add(&mut self, index: usize, item: T) {
+        RootishArrayStack::add(self, index, item)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index < self.len {
+            Some(RootishArrayStack::remove(self, index))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{i2b, isqrt, RootishArrayStack};
+    use crate::error::OdsError;
+    use crate::stack::Stack;
+    use std::vec::Vec;
+
+    fn capacity<T>(arr: &RootishArrayStack<T>) -> usize {
+        let blocks = arr.blocks.len();
+        blocks * (blocks + 1) / 2
+    }
+
+    fn assert_space_bound<T>(arr: &RootishArrayStack<T>) {
+        let len = arr.len();
+        let blocks = arr.blocks.len();
+        assert!(capacity(arr) >= len);
+        // Wasted space is bounded by 2 * blocks, with blocks in O(sqrt(n))
+        assert!(
+            capacity(arr) - len <= 2 * blocks,
+            "len: {}, capacity: {}",
+            len,
+            capacity(arr)
+        );
+        assert!(
+            blocks <= isqrt(2 * len) + 2,
+            "len: {}, blocks: {}",
+            len,
+            blocks
+        );
+    }
+
+    #[test]
+    fn isqrt_should_round_down() {
+        for n in 0..10_000 {
+            let root = isqrt(n);
+            assert!(root * root <= n);
+            assert!((root + 1) * (root + 1) > n);
+        }
+        let max = isqrt(usize::MAX);
+        assert!(max.checked_mul(max).is_some());
+        assert_eq!(max.checked_add(1).and_then(|m| m.checked_mul(m)), None);
+    }
+
+    #[test]
+    fn i2b_should_map_indices_to_blocks() {
+        let mut index = 0;
+        for block in 0..200 {
+            for _ in 0..=block {
+                assert_eq!(i2b(index), block);
+                index += 1;
+            }
+        }
+        // Large indices, where floating point precision is not enough, up to the last block
+        // whose indices can be doubled without overflowing (3_037_000_498 on 64-bit targets)
+        let max = isqrt(usize::MAX / 2) - 1;
+        for block in [1 << (usize::BITS / 2 - 1), max - max / 64, max] {
+            let first: usize = block * (block + 1) / 2;
+            assert_eq!(i2b(first), block);
+            assert_eq!(i2b(first - 1), block - 1);
+            assert_eq!(i2b(first + block), block);
+            assert_eq!(i2b(first + block + 1), block + 1);
+        }
+    }
+
+    #[test]
+    fn should_add_elements() {
+        let mut arr = RootishArrayStack::new();
+        arr.add(0, "foo");
+        arr.add(1, "bar");
+        arr.add(2, "foobar");
+        assert_eq!(arr.get(0), Some(&"foo"));
+        assert_eq!(arr.get(1), Some(&"bar"));
+        assert_eq!(arr.get(2), Some(&"foobar"));
+        assert_eq!(arr.get(3), None);
+    }
+
+    #[test]
+    fn should_insert_and_remove_elements() {
+        let mut arr = RootishArrayStack::new();
+        let mut vec = Vec::new();
+        for i in 0..200 {
+            arr.add(i / 2, i);
+            vec.insert(i / 2, i);
+            assert_space_bound(&arr);
+        }
+        for i in 0..200 {
+            assert_eq!(arr.get(i), vec.get(i));
+        }
+        *arr.get_mut(10).unwrap() = 1000;
+        vec[10] = 1000;
+        while !vec.is_empty() {
+            let index = vec.len() / 3;
+            assert_eq!(arr.remove(index), vec.remove(index));
+            assert_space_bound(&arr);
+        }
+        assert!(arr.is_empty());
+        assert_eq!(arr.blocks.len(), 0);
+    }
+
+    #[test]
+    fn should_be_a_stack() {
+        let mut arr = RootishArrayStack::new();
+        for i in 0..100 {
+            arr.push(i);
+            assert_space_bound(&arr);
+        }
+        for i in (0..100).rev() {
+            assert_eq!(arr.pop(), Some(i));
+            assert_space_bound(&arr);
+        }
+        assert_eq!(arr.pop(), None);
+    }
+
+    #[test]
+    fn should_clear_and_truncate_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let mut arr = RootishArrayStack::new();
+        for _ in 0..100 {
+            arr.push(Rc::clone(&watcher));
+        }
+        arr.truncate(200);
+        assert_eq!(arr.len(), 100);
+        arr.truncate(30);
+        assert_eq!(arr.len(), 30);
+        assert_eq!(Rc::strong_count(&watcher), 31);
+        assert_space_bound(&arr);
+        arr.clear();
+        assert!(arr.is_empty());
+        assert_eq!(Rc::strong_count(&watcher), 1);
+        assert_space_bound(&arr);
+    }
+
+    #[test]
+    fn should_try_to_add_and_remove_elements() {
+        let mut arr = RootishArrayStack::new();
+        arr.try_reserve(10).unwrap();
+        assert_eq!(arr.blocks.len(), 4);
+        for i in 0..10 {
+            arr.try_push(i).unwrap();
+        }
+        assert_eq!(arr.blocks.len(), 4);
+        let error = arr.try_add(12, 12).unwrap_err();
+        assert_eq!(
+            error.error(),
+            OdsError::IndexOutOfBounds { index: 12, len: 10 }
+        );
+        assert_eq!(error.into_element(), 12);
+        assert_eq!(arr.try_add(0, 10), Ok(()));
+        assert_eq!(arr.try_remove(0), Ok(10));
+        assert_eq!(
+            arr.try_remove(10),
+            Err(OdsError::IndexOutOfBounds { index: 10, len: 10 })
+        );
+        assert_eq!(
+            arr.try_reserve(usize::MAX),
+            Err(OdsError::capacity_overflow::<i32>())
+        );
+        assert_space_bound(&arr);
+    }
+
+    #[test]
+    fn should_drop_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        {
+            let mut arr = RootishArrayStack::new();
+            for _ in 0..20 {
+                arr.push(Rc::clone(&watcher));
+            }
+            assert_eq!(Rc::strong_count(&watcher), 21);
+        }
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+}
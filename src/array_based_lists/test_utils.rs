@@ -0,0 +1,84 @@
+//! Fixtures shared by the tests of the array-based lists
+
+use crate::queue::Queue;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::format;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+
+/// An element counting the comparisons made on it
+#[derive(Debug)]
+pub(crate) struct Counted<'a> {
+    pub(crate) value: i32,
+    pub(crate) comparisons: &'a Cell<usize>,
+}
+
+impl PartialEq for Counted<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Counted<'_> {}
+
+impl PartialOrd for Counted<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Counted<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.comparisons.set(self.comparisons.get() + 1);
+        self.value.cmp(&other.value)
+    }
+}
+
+/// Build a ring wrapping around the end of its buffer of 16 slots, along with its reference
+pub(crate) fn wrapped_ring<R>() -> (R, VecDeque<i32>)
+where
+    R: Queue<Item = i32> + FromIterator<i32> + Extend<i32>,
+{
+    let mut ring: R = (0..12).collect();
+    let mut deque: VecDeque<_> = (0..12).collect();
+    for _ in 0..5 {
+        ring.remove();
+        deque.pop_front();
+    }
+    ring.extend(12..20);
+    deque.extend(12..20);
+    (ring, deque)
+}
+
+/// The hash of given value
+pub(crate) fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Check that `arr` and `other`, holding the same elements laid out differently, compare,
+/// hash and format like their elements
+///
+/// The elements of `arr` must be non-negative.
+pub(crate) fn assert_compare_by_elements<C>(arr: C, other: C)
+where
+    C: Clone + Ord + Hash + Debug + FromIterator<i32> + Extend<i32>,
+{
+    assert_eq!(arr, other);
+    assert_eq!(hash(&arr), hash(&other));
+    assert_eq!(arr.cmp(&other), Ordering::Equal);
+
+    let mut clone = arr.clone();
+    assert_eq!(clone, arr);
+    clone.extend(Some(0));
+    assert_ne!(clone, arr);
+    assert!(clone > arr);
+    let smaller: C = (-1..5).collect();
+    assert!(smaller < arr);
+    assert_eq!(format!("{:?}", smaller), "[-1, 0, 1, 2, 3, 4]");
+}
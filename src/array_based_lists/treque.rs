@@ -0,0 +1,601 @@
+//! # Treque: Fast Insertions in the Middle with Two Deques
+//!
+//! See Chapter 2, Exercise 2.3
+
+use crate::array_based_lists::array_deque::{self, ArrayDeque};
+use crate::deque::Deque;
+use crate::error::{InsertError, OdsError};
+use crate::list::List;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{Chain, FromIterator, FusedIterator};
+use core::ops::{Index, IndexMut};
+
+/// Treque: a deque also fast at inserting and removing in its middle
+///
+/// The first `len / 2` elements are stored in `front`, the following ones in `back`,
+/// so that both ends and the middle of the treque are ends of a deque.
+pub struct Treque<T> {
+    front: ArrayDeque<T>,
+    back: ArrayDeque<T>,
+}
+
+impl<T> Treque<T> {
+    /// Create a new Treque
+    pub fn new() -> Self {
+        Treque {
+            front: ArrayDeque::new(),
+            back: ArrayDeque::new(),
+        }
+    }
+
+    /// The treque length
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Is the treque empty ?
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty() && self.back.is_empty()
+    }
+
+    /// Get a reference to element at given index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let front_len = self.front.len();
+        if index < front_len {
+            self.front.get(index)
+        } else {
+            self.back.get(index - front_len)
+        }
+    }
+
+    /// Get a mutable reference to element at given index
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let front_len = self.front.len();
+        if index < front_len {
+            self.front.get_mut(index)
+        } else {
+            self.back.get_mut(index - front_len)
+        }
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// Runs in O(1 + min(i, n - i, |n/2 - i|)).
+    ///
+    /// # Panics
+    /// Panics if `index` > `len`
+    pub fn add(&mut self, index: usize, element: T) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+        let len = self.len();
+        if index > len {
+            assert_failed(index, len);
+        }
+
+        let front_len = self.front.len();
+        if index < front_len {
+            self.front.add(index, element);
+        } else {
+            self.back.add(index - front_len, element);
+        }
+        self.balance();
+    }
+
+    /// Remove and return element at given index, shifting all following elements to the left
+    ///
+    /// Runs in O(1 + min(i, n - i, |n/2 - i|)).
+    /// Returns `None` if `index` >= `len`
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let front_len = self.front.len();
+        let element = if index < front_len {
+            self.front.remove(index)
+        } else {
+            self.back.remove(index - front_len)
+        };
+        if element.is_some() {
+            self.balance();
+        }
+        element
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// Hands the element back if `index` > `len` or if the deques cannot grow.
+    pub fn try_add(&mut self, index: usize, element: T) -> Result<(), InsertError<T>> {
+        let len = self.len();
+        if index > len {
+            let error = OdsError::IndexOutOfBounds { index, len };
+            return Err(InsertError::new(error, element));
+        }
+        if let Err(error) = self.try_reserve(1) {
+            return Err(InsertError::new(error, element));
+        }
+        self.add(index, element);
+        Ok(())
+    }
+
+    /// Remove and return element at given index, shifting all following elements to the left
+    ///
+    /// Fails if `index` >= `len`
+    pub fn try_remove(&mut self, index: usize) -> Result<T, OdsError> {
+        let len = self.len();
+        self.remove(index)
+            .ok_or(OdsError::IndexOutOfBounds { index, len })
+    }
+
+    /// Insert given element at the back of the deque
+    ///
+    /// Hands the element back if the deques cannot grow.
+    pub fn try_push(&mut self, element: T) -> Result<(), InsertError<T>> {
+        self.try_add(self.len(), element)
+    }
+
+    /// Make room for at least `additional` more elements
+    ///
+    /// Each deque is grown to hold its half of the elements, plus the one `balance` may move.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), OdsError> {
+        let required = self
+            .len()
+            .checked_add(additional)
+            .ok_or_else(OdsError::capacity_overflow::<T>)?;
+        let half = required / 2 + 1;
+        self.front
+            .try_reserve(half.saturating_sub(self.front.len()))?;
+        self.back.try_reserve(half.saturating_sub(self.back.len()))
+    }
+
+    /// Iterate over references to the elements, from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.front.iter().chain(self.back.iter()),
+        }
+    }
+
+    /// Iterate over mutable references to the elements, from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            inner: self.front.iter_mut().chain(self.back.iter_mut()),
+        }
+    }
+
+    /// Drop all the elements, keeping the allocated capacity
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+    }
+
+    /// Move elements between the deques until `front` holds the first `len / 2` ones
+    ///
+    /// A single `add` or `remove` unbalances the deques by one element at most.
+    fn balance(&mut self) {
+        let half = self.len() / 2;
+        while self.front.len() < half {
+            let element = self.back.remove_first().expect("back deque is too small");
+            self.front.add_last(element);
+        }
+        while self.front.len() > half {
+            let element = self.front.remove_last().expect("front deque is too small");
+            self.back.add_first(element);
+        }
+    }
+}
+
+/// An iterator over references to the elements of a `Treque`
+pub struct Iter<'a, T> {
+    inner: Chain<array_deque::Iter<'a, T>, array_deque::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over mutable references to the elements of a `Treque`
+pub struct IterMut<'a, T> {
+    inner: Chain<array_deque::IterMut<'a, T>, array_deque::IterMut<'a, T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a `Treque`
+pub struct IntoIter<T> {
+    inner: Chain<array_deque::IntoIter<T>, array_deque::IntoIter<T>>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for Treque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            inner: self.front.into_iter().chain(self.back),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Treque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Treque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for Treque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut arr = Self::new();
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T> Extend<T> for Treque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.back.extend(iter);
+        self.balance();
+    }
+}
+
+impl<T: Clone> Clone for Treque<T> {
+    fn clone(&self) -> Self {
+        Treque {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Treque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Treque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Treque<T> {}
+
+impl<T: PartialOrd> PartialOrd for Treque<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for Treque<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for Treque<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        self.iter().for_each(|element| element.hash(state));
+    }
+}
+
+impl<T> Index<usize> for Treque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        match self.get(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Treque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+impl<T> Default for Treque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> List for Treque<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        Treque::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        Treque::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        Treque::get_mut(self, index)
+    }
+
+    fn add(&mut self, index: usize, item: T) {
+        Treque::add(self, index, item)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        Treque::remove(self, index)
+    }
+}
+
+impl<T> Deque for Treque<T> {
+    type Item = T;
+
+    fn add_first(&mut self, item: T) {
+        self.add(0, item)
+    }
+
+    fn add_last(&mut self, item: T) {
+        self.add(self.len(), item)
+    }
+
+    fn remove_first(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn remove_last(&mut self) -> Option<T> {
+        self.len()
+            .checked_sub(1)
+            .and_then(|index| self.remove(index))
+    }
+
+    fn peek_first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    fn peek_last(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Treque;
+    use crate::array_based_lists::test_utils;
+    use crate::deque::Deque;
+    use crate::error::OdsError;
+    use crate::list::List;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::VecDeque;
+
+    fn assert_balanced<T>(arr: &Treque<T>) {
+        assert_eq!(arr.front.len(), arr.len() / 2);
+    }
+
+    #[test]
+    fn should_add_and_remove_elements() {
+        let mut arr = Treque::new();
+        arr.add(0, "foo");
+        arr.add(1, "bar");
+        arr.add(1, "foobar");
+        assert_eq!(arr.get(0), Some(&"foo"));
+        assert_eq!(arr.get(1), Some(&"foobar"));
+        assert_eq!(arr.get(2), Some(&"bar"));
+        assert_eq!(arr.get(3), None);
+        assert_eq!(arr.remove(3), None);
+        assert_eq!(arr.remove(1), Some("foobar"));
+        assert_eq!(arr.remove(0), Some("foo"));
+        assert_eq!(arr.remove(0), Some("bar"));
+        assert_eq!(arr.remove(0), None);
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn should_set_elements() {
+        let mut arr: Treque<_> = (0..10).collect();
+        for i in 0..10 {
+            assert_eq!(arr.set(i, 10 * i), i);
+        }
+        arr[3] = 42;
+        assert!(arr
+            .iter()
+            .copied()
+            .eq([0, 10, 20, 42, 40, 50, 60, 70, 80, 90]));
+    }
+
+    #[test]
+    fn should_be_a_deque() {
+        let mut arr = Treque::new();
+        arr.add_last(1);
+        arr.add_first(0);
+        arr.add_last(2);
+        assert_eq!(arr.peek_first(), Some(&0));
+        assert_eq!(arr.peek_last(), Some(&2));
+        assert_eq!(arr.remove_last(), Some(2));
+        assert_eq!(arr.remove_first(), Some(0));
+        assert_eq!(arr.remove_first(), Some(1));
+        assert_eq!(arr.remove_last(), None);
+    }
+
+    #[test]
+    fn should_stay_balanced_when_adding_in_the_middle() {
+        let mut arr = Treque::new();
+        let mut deque = VecDeque::new();
+        for i in 0..100 {
+            arr.add(arr.len() / 2, i);
+            deque.insert(deque.len() / 2, i);
+            assert_balanced(&arr);
+        }
+        assert!(arr.iter().eq(deque.iter()));
+        assert!(arr.iter().rev().eq(deque.iter().rev()));
+    }
+
+    #[test]
+    fn should_behave_like_vec_deque() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut arr = Treque::new();
+        let mut deque = VecDeque::new();
+        for i in 0..2000 {
+            let len = deque.len();
+            if len == 0 || rng.gen_ratio(3, 5) {
+                let index = rng.gen_range(0..=len);
+                arr.add(index, i);
+                deque.insert(index, i);
+            } else {
+                let index = rng.gen_range(0..len);
+                assert_eq!(arr.remove(index), deque.remove(index));
+            }
+            assert_balanced(&arr);
+            assert_eq!(arr.len(), deque.len());
+            if !deque.is_empty() {
+                let index = rng.gen_range(0..deque.len());
+                assert_eq!(arr.get(index), deque.get(index));
+            }
+        }
+        assert!(arr.iter().eq(deque.iter()));
+        assert!(arr.into_iter().eq(deque));
+    }
+
+    #[test]
+    fn should_collect_and_iterate_mutably() {
+        let mut arr: Treque<_> = (0..7).collect();
+        assert_balanced(&arr);
+        assert_eq!(arr.iter().len(), 7);
+        for element in &mut arr {
+            *element *= 2;
+        }
+        assert!(arr.iter().copied().eq((0..14).step_by(2)));
+        arr.clear();
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn should_not_reallocate_after_reserving() {
+        let mut arr = Treque::new();
+        arr.try_reserve(100).unwrap();
+        let capacities = (arr.front.capacity(), arr.back.capacity());
+        for i in 0..100 {
+            arr.try_add(i / 2, i).unwrap();
+            assert_balanced(&arr);
+        }
+        assert_eq!((arr.front.capacity(), arr.back.capacity()), capacities);
+        let error = arr.try_add(101, 101).unwrap_err();
+        assert_eq!(
+            error.error(),
+            OdsError::IndexOutOfBounds {
+                index: 101,
+                len: 100
+            }
+        );
+        assert_eq!(arr.try_remove(50), Ok(98));
+        assert_eq!(
+            arr.try_remove(99),
+            Err(OdsError::IndexOutOfBounds { index: 99, len: 99 })
+        );
+        arr.try_push(99).unwrap();
+        assert_eq!(arr.get(99), Some(&99));
+        assert_eq!((arr.front.capacity(), arr.back.capacity()), capacities);
+        assert_eq!(
+            arr.try_reserve(usize::MAX),
+            Err(OdsError::capacity_overflow::<usize>())
+        );
+    }
+
+    #[test]
+    fn should_compare_elements_regardless_of_layout() {
+        let arr: Treque<_> = (0..10).collect();
+        let other = Treque {
+            front: (0..2).collect(),
+            back: (2..10).collect(),
+        };
+        assert_ne!(arr.front.len(), other.front.len());
+        test_utils::assert_compare_by_elements(arr, other);
+    }
+
+    #[test]
+    #[should_panic(expected = "index (is 7) should be < len (is 7)")]
+    fn should_panic_when_indexing_out_of_bounds() {
+        let arr: Treque<_> = (0..7).collect();
+        let _ = arr[7];
+    }
+}
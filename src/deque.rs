@@ -0,0 +1,26 @@
+//! A double-ended queue container
+//!
+//! See `Deque`
+
+/// A double-ended queue container
+pub trait Deque {
+    type Item;
+
+    /// Insert a new item in front of the deque
+    fn add_first(&mut self, item: Self::Item);
+
+    /// Insert a new item at the end of the deque
+    fn add_last(&mut self, item: Self::Item);
+
+    /// Remove and return the item in front of the deque
+    fn remove_first(&mut self) -> Option<Self::Item>;
+
+    /// Remove and return the item at the end of the deque
+    fn remove_last(&mut self) -> Option<Self::Item>;
+
+    /// Get a reference to the item in front of the deque
+    fn peek_first(&self) -> Option<&Self::Item>;
+
+    /// Get a reference to the item at the end of the deque
+    fn peek_last(&self) -> Option<&Self::Item>;
+}
@@ -0,0 +1,136 @@
+//! Errors reported by the fallible operations of the containers
+//!
+//! See `OdsError`
+//!
+//! The array-based containers pair `add`, `remove`, `push` and `reserve` with `try_add`,
+//! `try_remove`, `try_push` and `try_reserve`. Queues only add at their end, so they have
+//! no `push` nor `try_push`, and their `try_remove` fails with `OdsError::Empty`.
+
+use core::{fmt, mem};
+
+/// The reason a fallible operation failed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OdsError {
+    /// The index is out of the bounds of the container
+    IndexOutOfBounds { index: usize, len: usize },
+    /// The container holds no element to remove
+    Empty,
+    /// The container cannot hold that many elements
+    CapacityExceeded { capacity: usize },
+    /// The allocator could not provide the requested storage
+    AllocationFailed,
+}
+
+impl OdsError {
+    /// The error of a buffer asked for more elements of type `T` than can be allocated
+    pub(crate) fn capacity_overflow<T>() -> Self {
+        let capacity = match mem::size_of::<T>() {
+            0 => usize::MAX,
+            size => isize::MAX as usize / size,
+        };
+        OdsError::CapacityExceeded { capacity }
+    }
+}
+
+impl fmt::Display for OdsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OdsError::IndexOutOfBounds { index, len } => {
+                write!(f, "index (is {}) is out of bounds (len is {})", index, len)
+            }
+            OdsError::Empty => f.write_str("the container is empty"),
+            OdsError::CapacityExceeded { capacity } => {
+                write!(f, "capacity exceeded (capacity is {})", capacity)
+            }
+            OdsError::AllocationFailed => f.write_str("memory allocation failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OdsError {}
+
+/// The error of a fallible insertion, handing the rejected element back
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InsertError<T> {
+    error: OdsError,
+    element: T,
+}
+
+impl<T> InsertError<T> {
+    pub(crate) fn new(error: OdsError, element: T) -> Self {
+        InsertError { error, element }
+    }
+
+    /// The reason the element was rejected
+    pub fn error(&self) -> OdsError {
+        self.error
+    }
+
+    /// The rejected element
+    pub fn into_element(self) -> T {
+        self.element
+    }
+}
+
+impl<T> fmt::Debug for InsertError<T> {
+    /// Format the error only, so the element does not need to implement `Debug`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InsertError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for InsertError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for InsertError<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{InsertError, OdsError};
+    use std::format;
+    use std::string::ToString;
+
+    #[test]
+    fn should_describe_errors() {
+        let error = OdsError::IndexOutOfBounds { index: 4, len: 3 };
+        assert_eq!(
+            error.to_string(),
+            "index (is 4) is out of bounds (len is 3)"
+        );
+        let error = InsertError::new(OdsError::CapacityExceeded { capacity: 8 }, "foo");
+        assert_eq!(error.to_string(), "capacity exceeded (capacity is 8)");
+        assert_eq!(
+            format!("{:?}", error),
+            "InsertError { error: CapacityExceeded { capacity: 8 }, .. }"
+        );
+        assert_eq!(error.into_element(), "foo");
+        assert_eq!(OdsError::Empty.to_string(), "the container is empty");
+        assert_eq!(
+            OdsError::AllocationFailed.to_string(),
+            "memory allocation failed"
+        );
+    }
+
+    #[test]
+    fn should_compute_capacity_overflows() {
+        assert_eq!(
+            OdsError::capacity_overflow::<u64>(),
+            OdsError::CapacityExceeded {
+                capacity: isize::MAX as usize / 8
+            }
+        );
+        assert_eq!(
+            OdsError::capacity_overflow::<()>(),
+            OdsError::CapacityExceeded {
+                capacity: usize::MAX
+            }
+        );
+    }
+}
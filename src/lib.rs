@@ -1,3 +1,13 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(test)]
+extern crate std;
+
 pub mod array_based_lists;
+pub mod deque;
+pub mod error;
+pub mod linked_lists;
+pub mod list;
 pub mod queue;
 pub mod stack;
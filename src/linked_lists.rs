@@ -0,0 +1,8 @@
+//! # Linked Lists
+//!
+//! See Chapter 3
+
+pub mod dl_list;
+pub mod se_list;
+pub mod sl_list;
+pub mod xor_list;
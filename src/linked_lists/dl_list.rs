@@ -0,0 +1,985 @@
+//! # DLList: A Doubly-Linked List
+//!
+//! See Chapter 3.2
+
+use crate::deque::Deque;
+use crate::list::List;
+use alloc::boxed::Box;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Index, IndexMut};
+use core::ptr::NonNull;
+
+/// DLList: a circular doubly-linked list
+///
+/// The nodes are linked in a cycle through a dummy node, which holds no element,
+/// so no link is ever missing. Elements are reached by walking from the nearest end.
+pub struct DLList<T> {
+    dummy: NonNull<Node<T>>,
+    len: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+struct Node<T> {
+    element: MaybeUninit<T>,
+    prev: NonNull<Node<T>>,
+    next: NonNull<Node<T>>,
+}
+
+// The list owns its nodes, like a `Box` would
+unsafe impl<T: Send> Send for DLList<T> {}
+unsafe impl<T: Sync> Sync for DLList<T> {}
+
+impl<T> DLList<T> {
+    /// Create a new, empty, DLList
+    ///
+    /// Allocates the dummy node.
+    pub fn new() -> Self {
+        let dummy = Box::new(Node {
+            element: MaybeUninit::uninit(),
+            prev: NonNull::dangling(),
+            next: NonNull::dangling(),
+        });
+        let dummy = NonNull::from(Box::leak(dummy));
+        unsafe {
+            (*dummy.as_ptr()).prev = dummy;
+            (*dummy.as_ptr()).next = dummy;
+        }
+        DLList {
+            dummy,
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// The list length
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the list empty ?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a reference to element at given index
+    ///
+    /// Runs in O(1 + min(i, n - i)).
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            Some(unsafe { (*self.node(index).as_ptr()).element.assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to element at given index
+    ///
+    /// Runs in O(1 + min(i, n - i)).
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            Some(unsafe { (*self.node(index).as_ptr()).element.assume_init_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// Replace the element at given index, returning the previous one
+    ///
+    /// Runs in O(1 + min(i, n - i)).
+    ///
+    /// # Panics
+    /// Panics if `index` >= `len`
+    pub fn set(&mut self, index: usize, element: T) -> T {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(old) => mem::replace(old, element),
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// Runs in O(1 + min(i, n - i)).
+    ///
+    /// # Panics
+    /// Panics if `index` > `len`
+    pub fn add(&mut self, index: usize, element: T) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+        let len = self.len;
+        if index > len {
+            assert_failed(index, len);
+        }
+        unsafe { self.link_before(self.node(index), element) };
+    }
+
+    /// Remove and return element at given index, shifting all following elements to the left
+    ///
+    /// Runs in O(1 + min(i, n - i)).
+    /// Returns `None` if `index` >= `len`
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index < self.len {
+            Some(unsafe { self.unlink(self.node(index)) })
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over references to the elements, from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        unsafe {
+            Iter {
+                front: (*self.dummy.as_ptr()).next,
+                back: (*self.dummy.as_ptr()).prev,
+                len: self.len,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    /// Iterate over mutable references to the elements, from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        unsafe {
+            IterMut {
+                front: (*self.dummy.as_ptr()).next,
+                back: (*self.dummy.as_ptr()).prev,
+                len: self.len,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    /// A cursor on the element at given index, or on the dummy node if `index` is `len`
+    ///
+    /// Runs in O(1 + min(i, n - i)).
+    ///
+    /// # Panics
+    /// Panics if `index` > `len`
+    pub fn cursor_mut(&mut self, index: usize) -> CursorMut<'_, T> {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("cursor index (is {}) should be <= len (is {})", index, len);
+        }
+        let len = self.len;
+        if index > len {
+            assert_failed(index, len);
+        }
+        CursorMut {
+            current: self.node(index),
+            index,
+            list: self,
+        }
+    }
+
+    /// A cursor on the front element, or on the dummy node if the list is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: unsafe { (*self.dummy.as_ptr()).next },
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// A cursor on the back element, or on the dummy node if the list is empty
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: unsafe { (*self.dummy.as_ptr()).prev },
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Drop all the elements
+    pub fn clear(&mut self) {
+        while self.remove_first().is_some() {}
+    }
+
+    /// The node at given index, walking from the nearest end
+    ///
+    /// `index` must be lower or equal to `len`, `len` being the dummy node.
+    fn node(&self, index: usize) -> NonNull<Node<T>> {
+        let mut node = self.dummy;
+        unsafe {
+            if index < self.len / 2 {
+                for _ in 0..=index {
+                    node = (*node.as_ptr()).next;
+                }
+            } else {
+                for _ in index..self.len {
+                    node = (*node.as_ptr()).prev;
+                }
+            }
+        }
+        node
+    }
+
+    /// Insert a new node holding given element before `next`
+    ///
+    /// # Safety
+    /// `next` must be a node of this list, possibly the dummy one
+    unsafe fn link_before(&mut self, next: NonNull<Node<T>>, element: T) {
+        let prev = (*next.as_ptr()).prev;
+        let node = Box::new(Node {
+            element: MaybeUninit::new(element),
+            prev,
+            next,
+        });
+        let node = NonNull::from(Box::leak(node));
+        (*prev.as_ptr()).next = node;
+        (*next.as_ptr()).prev = node;
+        self.len += 1;
+    }
+
+    /// Remove given node from the list, returning its element
+    ///
+    /// # Safety
+    /// `node` must be a node of this list, but not the dummy one
+    unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        let node = Box::from_raw(node.as_ptr());
+        (*node.prev.as_ptr()).next = node.next;
+        (*node.next.as_ptr()).prev = node.prev;
+        self.len -= 1;
+        node.element.assume_init()
+    }
+
+    /// Move all the nodes of `other` between `prev` and its next node
+    ///
+    /// # Safety
+    /// `prev` must be a node of this list, possibly the dummy one
+    unsafe fn splice_after(&mut self, prev: NonNull<Node<T>>, other: &mut DLList<T>) {
+        if other.is_empty() {
+            return;
+        }
+        let next = (*prev.as_ptr()).next;
+        let other_dummy = other.dummy.as_ptr();
+        let (first, last) = ((*other_dummy).next, (*other_dummy).prev);
+        (*prev.as_ptr()).next = first;
+        (*first.as_ptr()).prev = prev;
+        (*last.as_ptr()).next = next;
+        (*next.as_ptr()).prev = last;
+        (*other_dummy).next = other.dummy;
+        (*other_dummy).prev = other.dummy;
+        self.len += mem::replace(&mut other.len, 0);
+    }
+
+    /// Move the `count` nodes starting at `first` to a new list
+    ///
+    /// # Safety
+    /// The `count` nodes starting at `first` must be nodes of this list, none of them the dummy one
+    unsafe fn split_off_nodes(&mut self, first: NonNull<Node<T>>, count: usize) -> DLList<T> {
+        let mut other = DLList::new();
+        if count == 0 {
+            return other;
+        }
+        let mut last = first;
+        for _ in 1..count {
+            last = (*last.as_ptr()).next;
+        }
+        let (prev, next) = ((*first.as_ptr()).prev, (*last.as_ptr()).next);
+        (*prev.as_ptr()).next = next;
+        (*next.as_ptr()).prev = prev;
+        self.len -= count;
+
+        let other_dummy = other.dummy;
+        (*other_dummy.as_ptr()).next = first;
+        (*other_dummy.as_ptr()).prev = last;
+        (*first.as_ptr()).prev = other_dummy;
+        (*last.as_ptr()).next = other_dummy;
+        other.len = count;
+        other
+    }
+}
+
+/// A cursor over a `DLList`, able to edit the list around its position in O(1)
+///
+/// The cursor is either on an element, or on the dummy node sitting between the back and
+/// the front of the list. The dummy node is at index `len`.
+pub struct CursorMut<'a, T> {
+    list: &'a mut DLList<T>,
+    current: NonNull<Node<T>>,
+    index: usize,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// The index of the current element, `None` on the dummy node
+    pub fn index(&self) -> Option<usize> {
+        if self.is_dummy() {
+            None
+        } else {
+            Some(self.index)
+        }
+    }
+
+    /// Get a mutable reference to the current element, `None` on the dummy node
+    pub fn current(&mut self) -> Option<&mut T> {
+        if self.is_dummy() {
+            None
+        } else {
+            Some(unsafe { (*self.current.as_ptr()).element.assume_init_mut() })
+        }
+    }
+
+    /// Get a mutable reference to the next element, `None` if the next node is the dummy one
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = unsafe { (*self.current.as_ptr()).next };
+        if next == self.list.dummy {
+            None
+        } else {
+            Some(unsafe { (*next.as_ptr()).element.assume_init_mut() })
+        }
+    }
+
+    /// Get a mutable reference to the previous element, `None` if the previous node is the dummy one
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = unsafe { (*self.current.as_ptr()).prev };
+        if prev == self.list.dummy {
+            None
+        } else {
+            Some(unsafe { (*prev.as_ptr()).element.assume_init_mut() })
+        }
+    }
+
+    /// Move to the next node, going from the back element to the dummy node to the front element
+    pub fn move_next(&mut self) {
+        self.index = if self.is_dummy() { 0 } else { self.index + 1 };
+        self.current = unsafe { (*self.current.as_ptr()).next };
+    }
+
+    /// Move to the previous node, going from the front element to the dummy node to the back element
+    pub fn move_prev(&mut self) {
+        self.index = if self.is_dummy() {
+            self.list.len.saturating_sub(1)
+        } else if self.index == 0 {
+            self.list.len
+        } else {
+            self.index - 1
+        };
+        self.current = unsafe { (*self.current.as_ptr()).prev };
+    }
+
+    /// Insert given element before the cursor
+    ///
+    /// On the dummy node, the element is inserted at the back of the list.
+    pub fn insert_before(&mut self, element: T) {
+        unsafe { self.list.link_before(self.current, element) };
+        self.index += 1;
+    }
+
+    /// Insert given element after the cursor
+    ///
+    /// On the dummy node, the element is inserted at the front of the list.
+    pub fn insert_after(&mut self, element: T) {
+        let next = unsafe { (*self.current.as_ptr()).next };
+        unsafe { self.list.link_before(next, element) };
+        if self.is_dummy() {
+            self.index += 1;
+        }
+    }
+
+    /// Remove and return the current element, moving the cursor to the next node
+    ///
+    /// Returns `None` on the dummy node.
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.is_dummy() {
+            return None;
+        }
+        let node = self.current;
+        self.current = unsafe { (*node.as_ptr()).next };
+        Some(unsafe { self.list.unlink(node) })
+    }
+
+    /// Move all the elements of `other` before the cursor, leaving `other` empty
+    ///
+    /// On the dummy node, the elements are moved to the back of the list.
+    pub fn splice_before(&mut self, other: &mut DLList<T>) {
+        let count = other.len;
+        let prev = unsafe { (*self.current.as_ptr()).prev };
+        unsafe { self.list.splice_after(prev, other) };
+        self.index += count;
+    }
+
+    /// Move all the elements of `other` after the cursor, leaving `other` empty
+    ///
+    /// On the dummy node, the elements are moved to the front of the list.
+    pub fn splice_after(&mut self, other: &mut DLList<T>) {
+        let count = other.len;
+        unsafe { self.list.splice_after(self.current, other) };
+        if self.is_dummy() {
+            self.index += count;
+        }
+    }
+
+    /// Split the list before the cursor, returning the elements before it as a new list
+    ///
+    /// On the dummy node, all the elements are returned.
+    pub fn split_before(&mut self) -> DLList<T> {
+        let count = mem::replace(&mut self.index, 0);
+        unsafe {
+            let first = (*self.list.dummy.as_ptr()).next;
+            self.list.split_off_nodes(first, count)
+        }
+    }
+
+    /// Split the list after the cursor, returning the elements after it as a new list
+    ///
+    /// On the dummy node, all the elements are returned.
+    pub fn split_after(&mut self) -> DLList<T> {
+        let count = if self.is_dummy() {
+            self.index = 0;
+            self.list.len
+        } else {
+            self.list.len - self.index - 1
+        };
+        unsafe {
+            let first = (*self.current.as_ptr()).next;
+            self.list.split_off_nodes(first, count)
+        }
+    }
+
+    fn is_dummy(&self) -> bool {
+        self.current == self.list.dummy
+    }
+}
+
+/// An iterator over references to the elements of a `DLList`
+pub struct Iter<'a, T> {
+    front: NonNull<Node<T>>,
+    back: NonNull<Node<T>>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+// Only shared references to the elements are handed out
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = unsafe { &*self.front.as_ptr() };
+        self.front = node.next;
+        self.len -= 1;
+        Some(unsafe { node.element.assume_init_ref() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = unsafe { &*self.back.as_ptr() };
+        self.back = node.prev;
+        self.len -= 1;
+        Some(unsafe { node.element.assume_init_ref() })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+/// An iterator over mutable references to the elements of a `DLList`
+pub struct IterMut<'a, T> {
+    front: NonNull<Node<T>>,
+    back: NonNull<Node<T>>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+// Each element is handed out once, like a `&mut T`
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = unsafe { &mut *self.front.as_ptr() };
+        self.front = node.next;
+        self.len -= 1;
+        Some(unsafe { node.element.assume_init_mut() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = unsafe { &mut *self.back.as_ptr() };
+        self.back = node.prev;
+        self.len -= 1;
+        Some(unsafe { node.element.assume_init_mut() })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a `DLList`
+pub struct IntoIter<T> {
+    list: DLList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.remove_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.list.remove_last()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for DLList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DLList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DLList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for DLList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for DLList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.add_last(element);
+        }
+    }
+}
+
+impl<T: Clone> Clone for DLList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DLList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DLList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DLList<T> {}
+
+impl<T: Hash> Hash for DLList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.iter().for_each(|element| element.hash(state));
+    }
+}
+
+impl<T> Index<usize> for DLList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len;
+        match self.get(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for DLList<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+impl<T> Default for DLList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for DLList<T> {
+    fn drop(&mut self) {
+        self.clear();
+        // The dummy node holds no element to drop
+        drop(unsafe { Box::from_raw(self.dummy.as_ptr()) });
+    }
+}
+
+impl<T> List for DLList<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        DLList::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        DLList::get_mut(self, index)
+    }
+
+    fn add(&mut self, index: usize, item: T) {
+        DLList::add(self, index, item)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        DLList::remove(self, index)
+    }
+}
+
+impl<T> Deque for DLList<T> {
+    type Item = T;
+
+    fn add_first(&mut self, item: T) {
+        unsafe { self.link_before((*self.dummy.as_ptr()).next, item) };
+    }
+
+    fn add_last(&mut self, item: T) {
+        unsafe { self.link_before(self.dummy, item) };
+    }
+
+    fn remove_first(&mut self) -> Option<T> {
+        if self.len > 0 {
+            Some(unsafe { self.unlink((*self.dummy.as_ptr()).next) })
+        } else {
+            None
+        }
+    }
+
+    fn remove_last(&mut self) -> Option<T> {
+        if self.len > 0 {
+            Some(unsafe { self.unlink((*self.dummy.as_ptr()).prev) })
+        } else {
+            None
+        }
+    }
+
+    fn peek_first(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    fn peek_last(&self) -> Option<&T> {
+        self.iter().next_back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DLList;
+    use crate::deque::Deque;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn should_add_and_remove_elements() {
+        let mut list = DLList::new();
+        list.add(0, "foo");
+        list.add(1, "bar");
+        list.add(1, "foobar");
+        assert_eq!(list.get(0), Some(&"foo"));
+        assert_eq!(list.get(1), Some(&"foobar"));
+        assert_eq!(list.get(2), Some(&"bar"));
+        assert_eq!(list.get(3), None);
+        assert_eq!(list.set(2, "baz"), "bar");
+        assert_eq!(list.remove(3), None);
+        assert_eq!(list.remove(1), Some("foobar"));
+        assert_eq!(list.remove(1), Some("baz"));
+        assert_eq!(list.remove(0), Some("foo"));
+        assert_eq!(list.remove(0), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn should_behave_like_vec_deque() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut list = DLList::new();
+        let mut deque = VecDeque::new();
+        for _ in 0..10_000 {
+            let len = deque.len();
+            match rng.gen_range(0..6) {
+                0 | 1 => {
+                    let (index, element) = (rng.gen_range(0..=len), rng.gen::<i32>());
+                    list.add(index, element);
+                    deque.insert(index, element);
+                }
+                2 => {
+                    let index = rng.gen_range(0..=len);
+                    assert_eq!(list.remove(index), deque.remove(index));
+                }
+                3 => {
+                    let element = rng.gen::<i32>();
+                    list.add_first(element);
+                    deque.push_front(element);
+                }
+                4 => assert_eq!(list.remove_last(), deque.pop_back()),
+                _ => {
+                    let index = rng.gen_range(0..=len);
+                    assert_eq!(list.get_mut(index), deque.get_mut(index));
+                }
+            }
+            assert_eq!(list.len(), deque.len());
+            assert_eq!(list.peek_first(), deque.front());
+            assert_eq!(list.peek_last(), deque.back());
+        }
+        assert!(list.iter().eq(deque.iter()));
+        assert!(list.iter().rev().eq(deque.iter().rev()));
+    }
+
+    #[test]
+    fn should_iterate_from_both_ends() {
+        let mut list: DLList<_> = (0..10).collect();
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&9));
+        assert_eq!(iter.len(), 8);
+        assert!(iter.copied().eq(1..9));
+
+        for element in list.iter_mut().rev().take(5) {
+            *element *= 10;
+        }
+        assert!(list.iter().copied().eq([0, 1, 2, 3, 4, 50, 60, 70, 80, 90]));
+
+        let mut iter = list.clone().into_iter();
+        assert_eq!(iter.next_back(), Some(90));
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.len(), 8);
+        assert_eq!(list[5], 50);
+        list[5] = 5;
+        assert!(list.iter().copied().eq([0, 1, 2, 3, 4, 5, 60, 70, 80, 90]));
+    }
+
+    #[test]
+    fn should_move_cursor_around_the_dummy_node() {
+        let mut list: DLList<_> = (0..3).collect();
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+
+        let mut cursor = list.cursor_mut(1);
+        assert_eq!(cursor.current(), Some(&mut 1));
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.current(), Some(&mut 2));
+
+        let mut empty = DLList::<i32>::new();
+        let mut cursor = empty.cursor_back_mut();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    fn should_insert_and_remove_at_cursor() {
+        let mut list: DLList<_> = (0..5).collect();
+        let mut cursor = list.cursor_mut(2);
+        cursor.insert_before(10);
+        cursor.insert_after(20);
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 20));
+        assert_eq!(cursor.index(), Some(3));
+
+        let mut cursor = list.cursor_mut(6);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.insert_before(30);
+        cursor.insert_after(40);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        assert!(list.iter().copied().eq([40, 0, 1, 10, 20, 3, 4, 30]));
+
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(30));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(list.len(), 7);
+    }
+
+    #[test]
+    fn should_splice_lists_at_cursor() {
+        let mut list: DLList<_> = (0..4).collect();
+        let mut cursor = list.cursor_mut(2);
+        let mut other: DLList<_> = (10..13).collect();
+        cursor.splice_before(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(cursor.index(), Some(5));
+        let mut other: DLList<_> = (20..22).collect();
+        cursor.splice_after(&mut other);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        cursor.splice_after(&mut DLList::new());
+        assert!(list.iter().copied().eq([0, 1, 10, 11, 12, 2, 20, 21, 3]));
+
+        let mut cursor = list.cursor_mut(9);
+        cursor.splice_before(&mut (30..32).collect());
+        cursor.splice_after(&mut (40..42).collect());
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(12));
+        assert!(list
+            .iter()
+            .copied()
+            .eq([40, 41, 0, 1, 10, 11, 12, 2, 20, 21, 3, 30, 31]));
+        // The spliced list is still usable
+        other.add_last(50);
+        assert_eq!(other.len(), 1);
+    }
+
+    #[test]
+    fn should_split_lists_at_cursor() {
+        let mut list: DLList<_> = (0..10).collect();
+        let mut cursor = list.cursor_mut(4);
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        let after = cursor.split_after();
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert!(before.iter().copied().eq(0..4));
+        assert!(after.iter().copied().eq(5..10));
+        assert!(after.iter().rev().copied().eq((5..10).rev()));
+        assert!(list.iter().copied().eq([4]));
+
+        let mut list: DLList<_> = (0..10).collect();
+        let mut cursor = list.cursor_front_mut();
+        assert!(cursor.split_before().is_empty());
+        let mut cursor = list.cursor_mut(10);
+        let all = cursor.split_after();
+        assert_eq!(cursor.index(), None);
+        cursor.insert_before(20);
+        assert_eq!(cursor.index(), None);
+        assert!(all.iter().copied().eq(0..10));
+        assert!(list.iter().copied().eq([20]));
+
+        let mut list: DLList<_> = (0..10).collect();
+        let all = list.cursor_mut(10).split_before();
+        assert!(list.is_empty());
+        assert_eq!(all.len(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "cursor index (is 4) should be <= len (is 3)")]
+    fn should_panic_when_placing_cursor_out_of_bounds() {
+        let mut list: DLList<_> = (0..3).collect();
+        list.cursor_mut(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 4) should be <= len (is 3)")]
+    fn should_panic_when_adding_out_of_bounds() {
+        let mut list: DLList<_> = (0..3).collect();
+        list.add(4, 4);
+    }
+
+    #[test]
+    fn should_drop_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let mut list = DLList::new();
+        for _ in 0..10 {
+            list.add_last(Rc::clone(&watcher));
+        }
+        let mut other = list.cursor_mut(5).split_after();
+        assert_eq!(Rc::strong_count(&watcher), 11);
+        let mut iter = list.clone().into_iter();
+        iter.next();
+        iter.next_back();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&watcher), 11);
+        list.cursor_front_mut().splice_after(&mut other);
+        drop(other);
+        assert_eq!(list.len(), 10);
+        drop(list);
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+}
@@ -0,0 +1,870 @@
+//! # SEList: A Space-Efficient Linked List
+//!
+//! See Chapter 3.3
+
+use crate::array_based_lists::raw_buf::RawBuf;
+use crate::array_based_lists::ring;
+use crate::deque::Deque;
+use crate::linked_lists::dl_list::{self, CursorMut, DLList};
+use crate::list::List;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{Flatten, FromIterator, FusedIterator};
+use core::mem::{self, MaybeUninit};
+use core::ops::{Index, IndexMut};
+use core::ptr;
+
+/// The block size used by `SEList::default`
+pub const DEFAULT_BLOCK_SIZE: usize = 16;
+
+/// SEList: a doubly-linked list of blocks of elements
+///
+/// With a block size `b`, every block but the last one holds between `b - 1` and `b + 1`
+/// elements, so at most O(b + n / b) slots are wasted. Elements are reached in
+/// O(1 + min(i, n - i) / b), and added or removed in O(b + min(i, n - i) / b) amortized.
+pub struct SEList<T> {
+    blocks: DLList<BDeque<T>>,
+    block_size: usize,
+    len: usize,
+}
+
+impl<T> SEList<T> {
+    /// Create a new SEList with blocks of given size
+    ///
+    /// # Panics
+    /// Panics if `block_size` is 0
+    pub fn new(block_size: usize) -> Self {
+        assert!(block_size > 0, "block size should be positive");
+        SEList {
+            blocks: DLList::new(),
+            block_size,
+            len: 0,
+        }
+    }
+
+    /// The list length
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the list empty ?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of elements each block is sized for
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Get a reference to element at given index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            let (block, index) = self.locate(index);
+            self.blocks.get(block).and_then(|deque| deque.get(index))
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to element at given index
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            let (block, index) = self.locate(index);
+            self.blocks
+                .get_mut(block)
+                .and_then(|deque| deque.get_mut(index))
+        } else {
+            None
+        }
+    }
+
+    /// Insert given element at given index, shifting all following elements to the right
+    ///
+    /// # Panics
+    /// Panics if `index` > `len`
+    pub fn add(&mut self, index: usize, element: T) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+        let len = self.len;
+        if index > len {
+            assert_failed(index, len);
+        }
+        if index == len {
+            self.add_last(element);
+            return;
+        }
+
+        let b = self.block_size;
+        let (block, index) = self.locate(index);
+        let mut cursor = self.blocks.cursor_mut(block);
+        // Look for a block with a free slot among the `b` next ones
+        let mut r = 0;
+        while r < b && cursor.current().is_some_and(|deque| deque.is_full()) {
+            cursor.move_next();
+            r += 1;
+        }
+        if r == b {
+            for _ in 0..b {
+                cursor.move_prev();
+            }
+            spread(&mut cursor, b);
+            r = 0;
+        } else if cursor.index().is_none() {
+            cursor.insert_before(BDeque::new(b + 1));
+            cursor.move_prev();
+        }
+        // Make room in the first block by moving an element to each following block
+        for _ in 0..r {
+            let element = previous(&mut cursor).remove_last();
+            current(&mut cursor).add_first(element);
+            cursor.move_prev();
+        }
+        current(&mut cursor).add(index, element);
+        self.len += 1;
+    }
+
+    /// Remove and return element at given index, shifting all following elements to the left
+    ///
+    /// Returns `None` if `index` >= `len`
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let b = self.block_size;
+        let (block, index) = self.locate(index);
+        let mut cursor = self.blocks.cursor_mut(block);
+        // Look for a block with a spare element among the `b` next ones
+        let mut r = 0;
+        while r < b && cursor.current().is_some_and(|deque| deque.len() == b - 1) {
+            cursor.move_next();
+            r += 1;
+        }
+        for _ in 0..r {
+            cursor.move_prev();
+        }
+        if r == b {
+            gather(&mut cursor, b);
+        }
+        let element = current(&mut cursor).remove(index);
+        // Refill the block with an element of each following block, as long as needed
+        while current(&mut cursor).len() < b - 1 {
+            match cursor.peek_next() {
+                Some(next) => {
+                    let element = next.remove_first();
+                    current(&mut cursor).add_last(element);
+                    cursor.move_next();
+                }
+                None => break,
+            }
+        }
+        if current(&mut cursor).len() == 0 {
+            cursor.remove_current();
+        }
+        self.len -= 1;
+        Some(element)
+    }
+
+    /// Iterate over references to the elements, from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.blocks.iter().flatten(),
+            len: self.len,
+        }
+    }
+
+    /// Iterate over mutable references to the elements, from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            inner: self.blocks.iter_mut().flatten(),
+            len: self.len,
+        }
+    }
+
+    /// Drop all the elements and their blocks
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+        self.len = 0;
+    }
+
+    /// The index of the block holding the element at given index, and its index in that block
+    ///
+    /// Walks the blocks from the nearest end. `index` must be lower than `len`.
+    fn locate(&self, mut index: usize) -> (usize, usize) {
+        if index < self.len / 2 {
+            for (block, deque) in self.blocks.iter().enumerate() {
+                if index < deque.len() {
+                    return (block, index);
+                }
+                index -= deque.len();
+            }
+        } else {
+            let mut start = self.len;
+            for (block, deque) in self.blocks.iter().enumerate().rev() {
+                start -= deque.len();
+                if index >= start {
+                    return (block, index - start);
+                }
+            }
+        }
+        unreachable!("the blocks should hold `len` elements")
+    }
+}
+
+/// The block under the cursor
+fn current<'a, T>(cursor: &'a mut CursorMut<'_, BDeque<T>>) -> &'a mut BDeque<T> {
+    cursor.current().expect("cursor should be on a block")
+}
+
+/// The block before the cursor
+fn previous<'a, T>(cursor: &'a mut CursorMut<'_, BDeque<T>>) -> &'a mut BDeque<T> {
+    cursor.peek_prev().expect("cursor should follow a block")
+}
+
+/// Spread the elements of the `b` full blocks starting at the cursor over `b + 1` blocks
+/// of `b` elements, leaving the cursor on the first block
+fn spread<T>(cursor: &mut CursorMut<'_, BDeque<T>>, b: usize) {
+    for _ in 0..b {
+        cursor.move_next();
+    }
+    cursor.insert_before(BDeque::new(b + 1));
+    cursor.move_prev();
+    for _ in 0..b {
+        while current(cursor).len() < b {
+            let element = previous(cursor).remove_last();
+            current(cursor).add_first(element);
+        }
+        cursor.move_prev();
+    }
+}
+
+/// Gather the elements of the `b` blocks of `b - 1` elements starting at the cursor into
+/// `b - 1` blocks of `b` elements, leaving the cursor on the first block
+fn gather<T>(cursor: &mut CursorMut<'_, BDeque<T>>, b: usize) {
+    for _ in 0..b - 1 {
+        while current(cursor).len() < b {
+            let element = cursor
+                .peek_next()
+                .expect("cursor should precede a block")
+                .remove_first();
+            current(cursor).add_last(element);
+        }
+        cursor.move_next();
+    }
+    cursor.remove_current();
+    for _ in 0..b - 1 {
+        cursor.move_prev();
+    }
+}
+
+/// BDeque: a bounded deque, the block of an SEList
+///
+/// The elements are stored in a ring of fixed capacity, like an `ArrayDeque` that never grows.
+struct BDeque<T> {
+    buf: RawBuf<T>,
+    offset: usize,
+    len: usize,
+}
+
+impl<T> BDeque<T> {
+    /// Create a new BDeque, able to hold `capacity` elements
+    fn new(capacity: usize) -> Self {
+        BDeque {
+            buf: RawBuf::with_capacity(capacity),
+            offset: 0,
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == self.buf.capacity()
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            let position = ring::wrap(self.offset + index, self.buf.capacity());
+            Some(unsafe { &*self.buf[position].as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            let position = ring::wrap(self.offset + index, self.buf.capacity());
+            Some(unsafe { &mut *self.buf[position].as_mut_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Insert given element at given index, shifting the elements on the shorter side of `index`
+    ///
+    /// The deque must not be full, and `index` must be lower or equal to `len`.
+    fn add(&mut self, index: usize, element: T) {
+        debug_assert!(index <= self.len && !self.is_full());
+        let capacity = self.buf.capacity();
+        let (offset, len) = (self.offset, self.len);
+        let p = self.buf.as_mut_ptr();
+        if index < len / 2 {
+            // shift to the left 0..index
+            self.offset = offset.checked_sub(1).unwrap_or(capacity - 1);
+            unsafe { ring::shift_left(p, capacity, offset, index, 1) };
+        } else {
+            // shift to the right index..len
+            let start = ring::wrap(offset + index, capacity);
+            unsafe { ring::shift_right(p, capacity, start, len - index, 1) };
+        }
+        unsafe {
+            let position = ring::wrap(self.offset + index, capacity);
+            ptr::write(p.add(position), MaybeUninit::new(element));
+        }
+        self.len += 1;
+    }
+
+    /// Remove and return element at given index, shifting the elements on the shorter side
+    ///
+    /// `index` must be lower than `len`.
+    fn remove(&mut self, index: usize) -> T {
+        debug_assert!(index < self.len);
+        let capacity = self.buf.capacity();
+        let (offset, len) = (self.offset, self.len);
+        let p = self.buf.as_mut_ptr();
+        let element =
+            unsafe { ptr::read(p.add(ring::wrap(offset + index, capacity))).assume_init() };
+        if index < len / 2 {
+            // shift to the right 0..index
+            unsafe { ring::shift_right(p, capacity, offset, index, 1) };
+            self.offset = ring::wrap(offset + 1, capacity);
+        } else {
+            // shift to the left index + 1..len
+            let start = ring::wrap(offset + index + 1, capacity);
+            unsafe { ring::shift_left(p, capacity, start, len - index - 1, 1) };
+        }
+        self.len -= 1;
+        element
+    }
+
+    fn add_first(&mut self, element: T) {
+        self.add(0, element)
+    }
+
+    fn add_last(&mut self, element: T) {
+        self.add(self.len, element)
+    }
+
+    fn remove_first(&mut self) -> T {
+        self.remove(0)
+    }
+
+    fn remove_last(&mut self) -> T {
+        self.remove(self.len - 1)
+    }
+}
+
+impl<T> Drop for BDeque<T> {
+    fn drop(&mut self) {
+        let len = mem::replace(&mut self.len, 0);
+        unsafe {
+            let (front, back) = ring::as_mut_slices(&mut self.buf, self.offset, len);
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+impl<T> IntoIterator for BDeque<T> {
+    type Item = T;
+    type IntoIter = ring::IntoIter<T>;
+
+    fn into_iter(mut self) -> ring::IntoIter<T> {
+        let buf = mem::replace(&mut self.buf, RawBuf::new());
+        let len = mem::replace(&mut self.len, 0);
+        ring::IntoIter::new(buf, self.offset, len)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BDeque<T> {
+    type Item = &'a T;
+    type IntoIter = ring::Iter<'a, T>;
+
+    fn into_iter(self) -> ring::Iter<'a, T> {
+        ring::Iter::new(unsafe { ring::as_slices(&self.buf, self.offset, self.len) })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut BDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = ring::IterMut<'a, T>;
+
+    fn into_iter(self) -> ring::IterMut<'a, T> {
+        ring::IterMut::new(unsafe { ring::as_mut_slices(&mut self.buf, self.offset, self.len) })
+    }
+}
+
+/// An iterator over references to the elements of an `SEList`
+pub struct Iter<'a, T> {
+    inner: Flatten<dl_list::Iter<'a, BDeque<T>>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let element = self.inner.next()?;
+        self.len -= 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let element = self.inner.next_back()?;
+        self.len -= 1;
+        Some(element)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over mutable references to the elements of an `SEList`
+pub struct IterMut<'a, T> {
+    inner: Flatten<dl_list::IterMut<'a, BDeque<T>>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let element = self.inner.next()?;
+        self.len -= 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let element = self.inner.next_back()?;
+        self.len -= 1;
+        Some(element)
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of an `SEList`
+pub struct IntoIter<T> {
+    inner: Flatten<dl_list::IntoIter<BDeque<T>>>,
+    len: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let element = self.inner.next()?;
+        self.len -= 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        let element = self.inner.next_back()?;
+        self.len -= 1;
+        Some(element)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for SEList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            inner: self.blocks.into_iter().flatten(),
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SEList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SEList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for SEList<T> {
+    /// Collect the elements in a list of `DEFAULT_BLOCK_SIZE` blocks
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::default();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for SEList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.add_last(element);
+        }
+    }
+}
+
+impl<T: Clone> Clone for SEList<T> {
+    fn clone(&self) -> Self {
+        let mut list = SEList::new(self.block_size);
+        list.extend(self.iter().cloned());
+        list
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SEList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Lists are compared by their elements, regardless of their block sizes
+impl<T: PartialEq> PartialEq for SEList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for SEList<T> {}
+
+impl<T: Hash> Hash for SEList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.iter().for_each(|element| element.hash(state));
+    }
+}
+
+impl<T> Index<usize> for SEList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len;
+        match self.get(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for SEList<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+impl<T> Default for SEList<T> {
+    /// Create a new SEList with blocks of `DEFAULT_BLOCK_SIZE` elements
+    fn default() -> Self {
+        Self::new(DEFAULT_BLOCK_SIZE)
+    }
+}
+
+impl<T> List for SEList<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        SEList::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        SEList::get_mut(self, index)
+    }
+
+    fn add(&mut self, index: usize, item: T) {
+        SEList::add(self, index, item)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        SEList::remove(self, index)
+    }
+}
+
+impl<T> Deque for SEList<T> {
+    type Item = T;
+
+    fn add_first(&mut self, item: T) {
+        self.add(0, item)
+    }
+
+    /// Insert given element at the end of the list, in O(1)
+    fn add_last(&mut self, item: T) {
+        let b = self.block_size;
+        let mut cursor = self.blocks.cursor_back_mut();
+        if cursor.current().is_none_or(|deque| deque.is_full()) {
+            cursor.move_next();
+            cursor.insert_before(BDeque::new(b + 1));
+            cursor.move_prev();
+        }
+        current(&mut cursor).add_last(item);
+        self.len += 1;
+    }
+
+    fn remove_first(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn remove_last(&mut self) -> Option<T> {
+        let len = self.len;
+        if len > 0 {
+            self.remove(len - 1)
+        } else {
+            None
+        }
+    }
+
+    fn peek_first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    fn peek_last(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|index| self.get(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BDeque, SEList};
+    use crate::deque::Deque;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::VecDeque;
+    use std::vec::Vec;
+
+    /// Check every block but the last one holds `b - 1..=b + 1` elements,
+    /// the last one `1..=b + 1`, and the blocks hold all the elements
+    fn assert_blocks<T>(list: &SEList<T>) {
+        let b = list.block_size;
+        let sizes: Vec<_> = list.blocks.iter().map(|deque| deque.len()).collect();
+        assert!(
+            list.blocks
+                .iter()
+                .all(|deque| deque.buf.capacity() == b + 1),
+            "capacities should be {}",
+            b + 1
+        );
+        if let Some((last, others)) = sizes.split_last() {
+            assert!(
+                others.iter().all(|&size| b - 1 <= size && size <= b + 1),
+                "block size: {}, sizes: {:?}",
+                b,
+                sizes
+            );
+            assert!(
+                0 < *last && *last <= b + 1,
+                "block size: {}, sizes: {:?}",
+                b,
+                sizes
+            );
+        }
+        assert_eq!(sizes.iter().sum::<usize>(), list.len());
+    }
+
+    #[test]
+    fn should_add_and_remove_elements() {
+        let mut list = SEList::new(3);
+        list.add(0, "foo");
+        list.add(1, "bar");
+        list.add(1, "foobar");
+        assert_eq!(list.get(0), Some(&"foo"));
+        assert_eq!(list.get(1), Some(&"foobar"));
+        assert_eq!(list.get(2), Some(&"bar"));
+        assert_eq!(list.get(3), None);
+        assert_eq!(list.remove(3), None);
+        assert_eq!(list.remove(1), Some("foobar"));
+        assert_eq!(list.remove(0), Some("foo"));
+        assert_eq!(list.remove(0), Some("bar"));
+        assert_eq!(list.remove(0), None);
+        assert!(list.is_empty());
+        assert_eq!(list.blocks.len(), 0);
+    }
+
+    #[test]
+    fn should_keep_block_sizes_like_vec_deque() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for b in [1, 2, 3, 5, 8] {
+            let mut list = SEList::new(b);
+            let mut deque = VecDeque::new();
+            for step in 0..5_000 {
+                let len = deque.len();
+                // Grow then shrink, so that blocks are spread and gathered
+                let growing = (step / 1_000) % 2 == 0;
+                match rng.gen_range(0..10) {
+                    0..=3 if growing => {
+                        let (index, element) = (rng.gen_range(0..=len), rng.gen::<i32>());
+                        list.add(index, element);
+                        deque.insert(index, element);
+                    }
+                    0..=3 => {
+                        let index = rng.gen_range(0..=len);
+                        assert_eq!(list.remove(index), deque.remove(index));
+                    }
+                    4 | 5 if growing => {
+                        let element = rng.gen::<i32>();
+                        list.add_last(element);
+                        deque.push_back(element);
+                    }
+                    4 | 5 => assert_eq!(list.remove_first(), deque.pop_front()),
+                    6 => {
+                        let element = rng.gen::<i32>();
+                        list.add_first(element);
+                        deque.push_front(element);
+                    }
+                    7 => assert_eq!(list.remove_last(), deque.pop_back()),
+                    _ => {
+                        let index = rng.gen_range(0..=len);
+                        assert_eq!(list.get_mut(index), deque.get_mut(index));
+                    }
+                }
+                assert_blocks(&list);
+                assert_eq!(list.len(), deque.len());
+            }
+            assert!(list.iter().eq(deque.iter()));
+            assert!(list.iter().rev().eq(deque.iter().rev()));
+        }
+    }
+
+    #[test]
+    fn should_spread_full_blocks() {
+        let b = 3;
+        let mut list: SEList<_> = SEList::new(b);
+        list.extend(0..(b + 1) * b);
+        assert_eq!(list.blocks.len(), b);
+        // All the blocks are full, the insertion spreads them
+        list.add(0, 100);
+        assert_eq!(list.blocks.len(), b + 1);
+        assert_blocks(&list);
+        assert!(list.iter().copied().eq(Some(100).into_iter().chain(0..12)));
+    }
+
+    #[test]
+    fn should_gather_sparse_blocks() {
+        let b = 3;
+        let mut list = SEList::new(b);
+        // Build blocks of `b - 1` elements, the removal gathers them
+        for block in 0..b + 1 {
+            let mut deque = BDeque::new(b + 1);
+            for element in block * (b - 1)..(block + 1) * (b - 1) {
+                deque.add_last(element);
+            }
+            list.blocks.add(block, deque);
+        }
+        list.len = (b + 1) * (b - 1);
+        assert_blocks(&list);
+        list.remove(0);
+        assert_blocks(&list);
+        assert_eq!(list.blocks.len(), b);
+        assert!(list.iter().copied().eq(1..(b + 1) * (b - 1)));
+    }
+
+    #[test]
+    fn should_iterate_from_both_ends() {
+        let mut list = SEList::new(4);
+        list.extend(0..50);
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 50);
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&49));
+        assert_eq!(iter.len(), 48);
+        for element in list.iter_mut().rev() {
+            *element += 1;
+        }
+        assert!(list.iter().copied().eq(1..51));
+        list[10] = 0;
+        assert_eq!(list[10], 0);
+
+        let mut iter = list.clone().into_iter();
+        assert_eq!(iter.next_back(), Some(50));
+        assert_eq!(iter.len(), 49);
+        assert_eq!(list, list.clone());
+        let collected: SEList<_> = list.iter().copied().collect();
+        assert_eq!(collected.block_size(), super::DEFAULT_BLOCK_SIZE);
+        assert_eq!(collected, list);
+    }
+
+    #[test]
+    #[should_panic(expected = "block size should be positive")]
+    fn should_panic_without_block_size() {
+        SEList::<i32>::new(0);
+    }
+
+    #[test]
+    fn should_drop_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let mut list = SEList::new(3);
+        for i in 0..30 {
+            list.add(i / 2, Rc::clone(&watcher));
+        }
+        for i in 0..10 {
+            list.remove(i);
+        }
+        assert_eq!(Rc::strong_count(&watcher), 21);
+        let mut iter = list.clone().into_iter();
+        iter.next();
+        iter.next_back();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&watcher), 21);
+        drop(list);
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+}
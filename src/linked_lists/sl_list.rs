@@ -0,0 +1,514 @@
+//! # SLList: A Singly-Linked List
+//!
+//! See Chapter 3.1
+
+use crate::queue::Queue;
+use crate::stack::Stack;
+use alloc::boxed::Box;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+/// SLList: a singly-linked list
+///
+/// Elements are pushed and popped at the head, and added at the tail, all in O(1),
+/// so the list is both a stack and a queue.
+pub struct SLList<T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+struct Node<T> {
+    element: T,
+    next: Option<NonNull<Node<T>>>,
+}
+
+// The list owns its nodes, like a `Box` would
+unsafe impl<T: Send> Send for SLList<T> {}
+unsafe impl<T: Sync> Sync for SLList<T> {}
+
+impl<T> SLList<T> {
+    /// Create a new, empty, SLList
+    pub const fn new() -> Self {
+        SLList {
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// The list length
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the list empty ?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a reference to the element at the head of the list
+    pub fn peek(&self) -> Option<&T> {
+        self.head.map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    /// Get a mutable reference to the element at the head of the list
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Get a reference to the element at the tail of the list
+    pub fn peek_last(&self) -> Option<&T> {
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    /// Get a mutable reference to the element at the tail of the list
+    pub fn peek_last_mut(&mut self) -> Option<&mut T> {
+        self.tail
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Insert given element at the head of the list
+    pub fn push(&mut self, element: T) {
+        let node = Box::new(Node {
+            element,
+            next: self.head,
+        });
+        let node = NonNull::from(Box::leak(node));
+        if self.tail.is_none() {
+            self.tail = Some(node);
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    /// Remove and return the element at the head of the list
+    pub fn pop(&mut self) -> Option<T> {
+        self.head.map(|node| {
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            self.head = node.next;
+            if self.head.is_none() {
+                self.tail = None;
+            }
+            self.len -= 1;
+            node.element
+        })
+    }
+
+    /// Insert given element at the tail of the list
+    pub fn add(&mut self, element: T) {
+        let node = Box::new(Node {
+            element,
+            next: None,
+        });
+        let node = NonNull::from(Box::leak(node));
+        match self.tail {
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    /// Remove and return the element at the head of the list
+    ///
+    /// The head of the list is the front of the queue, so this is the same as `pop`.
+    pub fn remove(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    /// Reverse the order of the elements, relinking the nodes in place
+    pub fn reverse(&mut self) {
+        let mut previous = None;
+        let mut current = self.head;
+        while let Some(node) = current {
+            unsafe {
+                current = (*node.as_ptr()).next;
+                (*node.as_ptr()).next = previous;
+            }
+            previous = Some(node);
+        }
+        self.tail = self.head;
+        self.head = previous;
+    }
+
+    /// Iterate over references to the elements, from head to tail
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Iterate over mutable references to the elements, from head to tail
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Drop all the elements
+    ///
+    /// The nodes are released one at a time, so long lists do not overflow the call stack.
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+/// An iterator over references to the elements of an `SLList`
+pub struct Iter<'a, T> {
+    next: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+// Only shared references to the elements are handed out
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            let node = unsafe { &*node.as_ptr() };
+            self.next = node.next;
+            self.len -= 1;
+            &node.element
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+/// An iterator over mutable references to the elements of an `SLList`
+pub struct IterMut<'a, T> {
+    next: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+// Each element is handed out once, like a `&mut T`
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.next.map(|node| {
+            let node = unsafe { &mut *node.as_ptr() };
+            self.next = node.next;
+            self.len -= 1;
+            &mut node.element
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of an `SLList`
+pub struct IntoIter<T> {
+    list: SLList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for SLList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SLList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SLList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for SLList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for SLList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.add(element);
+        }
+    }
+}
+
+impl<T: Clone> Clone for SLList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SLList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for SLList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for SLList<T> {}
+
+impl<T: Hash> Hash for SLList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.iter().for_each(|element| element.hash(state));
+    }
+}
+
+impl<T> Default for SLList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for SLList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Stack for SLList<T> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        SLList::push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        SLList::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        SLList::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T> Queue for SLList<T> {
+    type Item = T;
+
+    fn add(&mut self, item: T) {
+        SLList::add(self, item)
+    }
+
+    fn remove(&mut self) -> Option<T> {
+        SLList::remove(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        SLList::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SLList;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn should_push_and_pop_elements() {
+        let mut list = SLList::new();
+        list.push("foo");
+        list.push("bar");
+        list.push("foobar");
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.peek(), Some(&"foobar"));
+        assert_eq!(list.peek_last(), Some(&"foo"));
+        assert_eq!(list.pop(), Some("foobar"));
+        assert_eq!(list.pop(), Some("bar"));
+        assert_eq!(list.pop(), Some("foo"));
+        assert_eq!(list.pop(), None);
+        assert_eq!(list.peek_last(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn should_add_and_remove_elements() {
+        let mut list = SLList::new();
+        list.add("foo");
+        list.add("bar");
+        list.push("foobar");
+        *list.peek_last_mut().unwrap() = "baz";
+        assert_eq!(list.remove(), Some("foobar"));
+        assert_eq!(list.remove(), Some("foo"));
+        assert_eq!(list.remove(), Some("baz"));
+        assert_eq!(list.remove(), None);
+        // The tail is reset once the list is empty
+        list.add("foo");
+        assert_eq!(list.peek(), Some(&"foo"));
+        assert_eq!(list.peek_last(), Some(&"foo"));
+    }
+
+    #[test]
+    fn should_behave_like_vec_deque() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut list = SLList::new();
+        let mut deque = VecDeque::new();
+        for _ in 0..10_000 {
+            match rng.gen_range(0..7) {
+                0 | 1 => {
+                    let element = rng.gen::<i32>();
+                    list.push(element);
+                    deque.push_front(element);
+                }
+                2 | 3 => {
+                    let element = rng.gen::<i32>();
+                    list.add(element);
+                    deque.push_back(element);
+                }
+                4 | 5 => assert_eq!(list.pop(), deque.pop_front()),
+                _ => {
+                    list.reverse();
+                    deque.make_contiguous().reverse();
+                }
+            }
+            assert_eq!(list.len(), deque.len());
+            assert_eq!(list.peek(), deque.front());
+            assert_eq!(list.peek_last(), deque.back());
+        }
+        assert!(list.iter().eq(deque.iter()));
+    }
+
+    #[test]
+    fn should_iterate_over_elements() {
+        let mut list: SLList<_> = (0..10).collect();
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.len(), 9);
+        assert!(iter.clone().eq(iter));
+
+        for element in &mut list {
+            *element *= 2;
+        }
+        assert!(list.iter().copied().eq((0..10).map(|i| i * 2)));
+
+        let mut iter = list.clone().into_iter();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.nth(4), Some(8));
+        assert_eq!(iter.len(), 5);
+        assert_eq!(list, (0..10).map(|i| i * 2).collect());
+    }
+
+    #[test]
+    fn should_reverse_elements() {
+        let mut list = SLList::<i32>::new();
+        list.reverse();
+        assert!(list.is_empty());
+        list.add(0);
+        list.reverse();
+        assert_eq!(list.peek_last(), Some(&0));
+
+        let mut list: SLList<_> = (0..5).collect();
+        list.reverse();
+        assert!(list.iter().copied().eq((0..5).rev()));
+        list.add(10);
+        list.push(20);
+        assert!(list.iter().copied().eq([20, 4, 3, 2, 1, 0, 10]));
+    }
+
+    #[test]
+    fn should_drop_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let mut list = SLList::new();
+        for _ in 0..10 {
+            list.add(Rc::clone(&watcher));
+        }
+        let clone = list.clone();
+        assert_eq!(Rc::strong_count(&watcher), 21);
+        list.pop();
+        assert_eq!(Rc::strong_count(&watcher), 20);
+        let mut iter = clone.into_iter();
+        iter.next();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&watcher), 10);
+        drop(list);
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+
+    #[test]
+    fn should_drop_long_lists() {
+        let list: SLList<_> = (0..1_000_000).collect();
+        drop(list);
+    }
+}
@@ -0,0 +1,604 @@
+//! # XorList: A XOR-Linked List
+//!
+//! See Chapter 3 exercises
+
+use crate::deque::Deque;
+use alloc::boxed::Box;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::NonNull;
+
+/// XorList: a doubly-linked list storing a single link per node
+///
+/// Each node stores the XOR of the addresses of its two neighbours, a missing neighbour
+/// having the address 0. Walking from a node to the next one requires the address of the
+/// previous one, so the list is only traversed from its ends. The links are symmetric,
+/// which makes reversing the list a matter of swapping its ends, in O(1).
+pub struct XorList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    element: T,
+    /// `address(prev) ^ address(next)`
+    link: usize,
+}
+
+// The list owns its nodes, like a `Box` would
+unsafe impl<T: Send> Send for XorList<T> {}
+unsafe impl<T: Sync> Sync for XorList<T> {}
+
+/// The address of a node, 0 for a missing one
+///
+/// The cast exposes the provenance of the pointer, so that `from_address` can rebuild it.
+fn address<T>(link: Link<T>) -> usize {
+    link.map_or(0, |node| node.as_ptr() as usize)
+}
+
+/// The node at given address, `None` for 0
+fn from_address<T>(address: usize) -> Link<T> {
+    NonNull::new(address as *mut Node<T>)
+}
+
+/// The neighbour of `node` that is not `from`
+///
+/// # Safety
+/// `node` must be a live node of a list, and `from` one of its neighbours
+unsafe fn step<T>(node: NonNull<Node<T>>, from: Link<T>) -> Link<T> {
+    from_address((*node.as_ptr()).link ^ address(from))
+}
+
+/// Insert a new node before `end`, the node at one end of a list, and make it the new end
+///
+/// `end` is either the head or the tail, and `other` is the opposite end.
+///
+/// # Safety
+/// `end` and `other` must be the two ends of the same list, both `None` if it is empty
+unsafe fn push<T>(end: &mut Link<T>, other: &mut Link<T>, element: T) {
+    let new = NonNull::from(Box::leak(Box::new(Node {
+        element,
+        link: address(*end),
+    })));
+    match *end {
+        // SAFETY: `end` is a live node of the list, whose outer neighbour was missing
+        Some(old) => (*old.as_ptr()).link ^= address(Some(new)),
+        None => *other = Some(new),
+    }
+    *end = Some(new);
+}
+
+/// Remove the node at one end of a list, and return its element
+///
+/// `end` is either the head or the tail, and `other` is the opposite end.
+///
+/// # Safety
+/// `end` and `other` must be the two ends of the same list, both `None` if it is empty
+unsafe fn pop<T>(end: &mut Link<T>, other: &mut Link<T>) -> Option<T> {
+    end.map(|old| {
+        let old_address = address(Some(old));
+        // SAFETY: the list owns its nodes, which were leaked from boxes in `push`
+        let old = Box::from_raw(old.as_ptr());
+        // The outer neighbour of an end is missing, so the link is the inner neighbour
+        *end = from_address(old.link);
+        match *end {
+            // SAFETY: the new end is a live node of the list, whose outer neighbour was `old`
+            Some(new) => (*new.as_ptr()).link ^= old_address,
+            None => *other = None,
+        }
+        old.element
+    })
+}
+
+impl<T> XorList<T> {
+    /// Create a new, empty, XorList
+    pub const fn new() -> Self {
+        XorList {
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// The list length
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the list empty ?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a reference to the element at the head of the list
+    pub fn peek_first(&self) -> Option<&T> {
+        self.head.map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    /// Get a mutable reference to the element at the head of the list
+    pub fn peek_first_mut(&mut self) -> Option<&mut T> {
+        self.head
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Get a reference to the element at the tail of the list
+    pub fn peek_last(&self) -> Option<&T> {
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    /// Get a mutable reference to the element at the tail of the list
+    pub fn peek_last_mut(&mut self) -> Option<&mut T> {
+        self.tail
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Insert given element at the head of the list
+    pub fn push_first(&mut self, element: T) {
+        // SAFETY: the head and the tail are the two ends of this list
+        unsafe { push(&mut self.head, &mut self.tail, element) };
+        self.len += 1;
+    }
+
+    /// Insert given element at the tail of the list
+    pub fn push_last(&mut self, element: T) {
+        // SAFETY: the head and the tail are the two ends of this list
+        unsafe { push(&mut self.tail, &mut self.head, element) };
+        self.len += 1;
+    }
+
+    /// Remove and return the element at the head of the list
+    pub fn pop_first(&mut self) -> Option<T> {
+        // SAFETY: the head and the tail are the two ends of this list
+        let element = unsafe { pop(&mut self.head, &mut self.tail) }?;
+        self.len -= 1;
+        Some(element)
+    }
+
+    /// Remove and return the element at the tail of the list
+    pub fn pop_last(&mut self) -> Option<T> {
+        // SAFETY: the head and the tail are the two ends of this list
+        let element = unsafe { pop(&mut self.tail, &mut self.head) }?;
+        self.len -= 1;
+        Some(element)
+    }
+
+    /// Reverse the order of the elements, in O(1)
+    pub fn reverse(&mut self) {
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    /// Iterate over references to the elements, from head to tail
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: (None, self.head),
+            tail: (None, self.tail),
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Iterate over mutable references to the elements, from head to tail
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: (None, self.head),
+            tail: (None, self.tail),
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Drop all the elements
+    ///
+    /// The nodes are released one at a time, so long lists do not overflow the call stack.
+    pub fn clear(&mut self) {
+        while self.pop_first().is_some() {}
+    }
+}
+
+/// Take a step from one end of an iterator, returning the node left behind
+///
+/// `end` holds the previously visited node and the next node to visit.
+///
+/// # Safety
+/// The next node must be a live node of a list, neighbour of the previously visited node
+unsafe fn advance<T>(end: &mut (Link<T>, Link<T>)) -> NonNull<Node<T>> {
+    let (previous, current) = *end;
+    let current = current.expect("iterator length should match its nodes");
+    *end = (Some(current), step(current, previous));
+    current
+}
+
+/// An iterator over references to the elements of an `XorList`
+pub struct Iter<'a, T> {
+    head: (Link<T>, Link<T>),
+    tail: (Link<T>, Link<T>),
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+// Only shared references to the elements are handed out
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `len` nodes remain between both ends, and the list is borrowed
+        Some(unsafe { &(*advance(&mut self.head).as_ptr()).element })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `len` nodes remain between both ends, and the list is borrowed
+        Some(unsafe { &(*advance(&mut self.tail).as_ptr()).element })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+/// An iterator over mutable references to the elements of an `XorList`
+pub struct IterMut<'a, T> {
+    head: (Link<T>, Link<T>),
+    tail: (Link<T>, Link<T>),
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+// Each element is handed out once, like a `&mut T`
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `len` nodes remain between both ends, each visited once
+        Some(unsafe { &mut (*advance(&mut self.head).as_ptr()).element })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `len` nodes remain between both ends, each visited once
+        Some(unsafe { &mut (*advance(&mut self.tail).as_ptr()).element })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of an `XorList`
+pub struct IntoIter<T> {
+    list: XorList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_last()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for XorList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a XorList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut XorList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for XorList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for XorList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push_last(element);
+        }
+    }
+}
+
+impl<T: Clone> Clone for XorList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for XorList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for XorList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for XorList<T> {}
+
+impl<T: Hash> Hash for XorList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.iter().for_each(|element| element.hash(state));
+    }
+}
+
+impl<T> Default for XorList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for XorList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Deque for XorList<T> {
+    type Item = T;
+
+    fn add_first(&mut self, item: T) {
+        self.push_first(item)
+    }
+
+    fn add_last(&mut self, item: T) {
+        self.push_last(item)
+    }
+
+    fn remove_first(&mut self) -> Option<T> {
+        self.pop_first()
+    }
+
+    fn remove_last(&mut self) -> Option<T> {
+        self.pop_last()
+    }
+
+    fn peek_first(&self) -> Option<&T> {
+        XorList::peek_first(self)
+    }
+
+    fn peek_last(&self) -> Option<&T> {
+        XorList::peek_last(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XorList;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn should_push_and_pop_at_both_ends() {
+        let mut list = XorList::new();
+        list.push_first("foo");
+        list.push_last("bar");
+        list.push_first("foobar");
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.peek_first(), Some(&"foobar"));
+        assert_eq!(list.peek_last(), Some(&"bar"));
+        *list.peek_last_mut().unwrap() = "baz";
+        assert_eq!(list.pop_last(), Some("baz"));
+        assert_eq!(list.pop_first(), Some("foobar"));
+        assert_eq!(list.pop_last(), Some("foo"));
+        assert_eq!(list.pop_first(), None);
+        assert_eq!(list.pop_last(), None);
+        assert_eq!(list.peek_last(), None);
+        assert!(list.is_empty());
+        // The ends are reset once the list is empty
+        list.push_last("foo");
+        assert_eq!(list.peek_first(), Some(&"foo"));
+        assert_eq!(list.peek_last(), Some(&"foo"));
+    }
+
+    #[test]
+    fn should_behave_like_vec_deque() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut list = XorList::new();
+        let mut deque = VecDeque::new();
+        for _ in 0..10_000 {
+            match rng.gen_range(0..12) {
+                0 | 1 => {
+                    let element = rng.gen::<i32>();
+                    list.push_first(element);
+                    deque.push_front(element);
+                }
+                2 | 3 => {
+                    let element = rng.gen::<i32>();
+                    list.push_last(element);
+                    deque.push_back(element);
+                }
+                4 => assert_eq!(list.pop_first(), deque.pop_front()),
+                5 => assert_eq!(list.pop_last(), deque.pop_back()),
+                6 => {
+                    list.reverse();
+                    deque.make_contiguous().reverse();
+                }
+                7 => {
+                    if let (Some(a), Some(b)) = (list.peek_first_mut(), deque.front_mut()) {
+                        *a += 1;
+                        *b += 1;
+                    }
+                }
+                8 => {
+                    // Meet in the middle from both ends
+                    let mut iter = list.iter();
+                    let mut expected = deque.iter();
+                    while iter.len() > 0 {
+                        assert_eq!(iter.next(), expected.next());
+                        assert_eq!(iter.next_back(), expected.next_back());
+                    }
+                    assert_eq!(expected.len(), 0);
+                }
+                _ => {}
+            }
+            assert_eq!(list.len(), deque.len());
+            assert_eq!(list.peek_first(), deque.front());
+            assert_eq!(list.peek_last(), deque.back());
+        }
+        assert!(list.iter().eq(deque.iter()));
+        assert!(list.iter().rev().eq(deque.iter().rev()));
+        assert!(list.into_iter().rev().eq(deque.into_iter().rev()));
+    }
+
+    #[test]
+    fn should_iterate_over_elements() {
+        let mut list: XorList<_> = (0..10).collect();
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&9));
+        assert_eq!(iter.len(), 8);
+        assert!(iter.clone().eq(iter));
+
+        for element in list.iter_mut().rev() {
+            *element *= 2;
+        }
+        assert!(list.iter().copied().eq((0..10).map(|i| i * 2)));
+
+        let mut iter = list.clone().into_iter();
+        assert_eq!(iter.next_back(), Some(18));
+        assert_eq!(iter.nth(4), Some(8));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(list, (0..10).map(|i| i * 2).collect());
+    }
+
+    #[test]
+    fn should_reverse_elements() {
+        let mut list = XorList::<i32>::new();
+        list.reverse();
+        assert!(list.is_empty());
+        list.push_last(0);
+        list.reverse();
+        assert_eq!(list.peek_last(), Some(&0));
+
+        let mut list: XorList<_> = (0..5).collect();
+        list.reverse();
+        assert!(list.iter().copied().eq((0..5).rev()));
+        list.push_last(10);
+        list.push_first(20);
+        assert!(list
+            .iter()
+            .copied()
+            .eq([20, 4, 3, 2, 1, 0, 10].iter().copied()));
+        list.reverse();
+        assert!(list
+            .iter()
+            .rev()
+            .copied()
+            .eq([20, 4, 3, 2, 1, 0, 10].iter().copied()));
+    }
+
+    #[test]
+    fn should_drop_elements() {
+        use std::rc::Rc;
+        let watcher = Rc::new(());
+        let mut list = XorList::new();
+        for _ in 0..10 {
+            list.push_last(Rc::clone(&watcher));
+        }
+        let clone = list.clone();
+        assert_eq!(Rc::strong_count(&watcher), 21);
+        list.pop_last();
+        assert_eq!(Rc::strong_count(&watcher), 20);
+        let mut iter = clone.into_iter();
+        iter.next();
+        iter.next_back();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&watcher), 10);
+        drop(list);
+        assert_eq!(Rc::strong_count(&watcher), 1);
+    }
+
+    #[test]
+    fn should_drop_long_lists() {
+        let list: XorList<_> = (0..1_000_000).collect();
+        drop(list);
+    }
+}
@@ -0,0 +1,127 @@
+//! A general indexed list container
+//!
+//! See `List`
+
+use core::mem;
+
+/// A sequence of items that can be accessed, inserted and removed by index
+pub trait List {
+    type Item;
+
+    /// The number of items in the list
+    fn len(&self) -> usize;
+
+    /// Is the list empty ?
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a reference to the item at given index
+    fn get(&self, index: usize) -> Option<&Self::Item>;
+
+    /// Get a mutable reference to the item at given index
+    fn get_mut(&mut self, index: usize) -> Option<&mut Self::Item>;
+
+    /// Replace the item at given index, returning the previous one
+    ///
+    /// # Panics
+    /// Panics if `index` >= `len`
+    fn set(&mut self, index: usize, item: Self::Item) -> Self::Item {
+        let len = self.len();
+        match self.get_mut(index) {
+            Some(old) => mem::replace(old, item),
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+
+    /// Insert given item at given index, shifting all following items to the right
+    ///
+    /// # Panics
+    /// Panics if `index` > `len`
+    fn add(&mut self, index: usize, item: Self::Item);
+
+    /// Remove and return the item at given index, shifting all following items to the left
+    ///
+    /// Returns `None` if `index` >= `len`
+    fn remove(&mut self, index: usize) -> Option<Self::Item>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::List;
+    use crate::array_based_lists::array_deque::ArrayDeque;
+    use crate::array_based_lists::array_stack::ArrayStack;
+    use crate::array_based_lists::dual_array_deque::DualArrayDeque;
+    use crate::array_based_lists::rootish_array_stack::RootishArrayStack;
+    use crate::array_based_lists::treque::Treque;
+    use crate::linked_lists::dl_list::DLList;
+    use crate::linked_lists::se_list::SEList;
+
+    fn check_list<L: List<Item = i32> + Default>() {
+        let mut list = L::default();
+        assert!(list.is_empty());
+        list.add(0, 1);
+        list.add(1, 3);
+        list.add(1, 2);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.get(0), Some(&1));
+        assert_eq!(list.get(1), Some(&2));
+        assert_eq!(list.get(2), Some(&3));
+        assert_eq!(list.get(3), None);
+
+        assert_eq!(list.set(1, 20), 2);
+        *list.get_mut(2).unwrap() = 30;
+        assert_eq!(list.get(1), Some(&20));
+        assert_eq!(list.get(2), Some(&30));
+
+        assert_eq!(list.remove(3), None);
+        assert_eq!(list.remove(1), Some(20));
+        assert_eq!(list.remove(0), Some(1));
+        assert_eq!(list.remove(0), Some(30));
+        assert_eq!(list.remove(0), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn array_stack_should_be_a_list() {
+        check_list::<ArrayStack<i32>>();
+    }
+
+    #[test]
+    fn array_deque_should_be_a_list() {
+        check_list::<ArrayDeque<i32>>();
+    }
+
+    #[test]
+    fn dual_array_deque_should_be_a_list() {
+        check_list::<DualArrayDeque<i32>>();
+    }
+
+    #[test]
+    fn rootish_array_stack_should_be_a_list() {
+        check_list::<RootishArrayStack<i32>>();
+    }
+
+    #[test]
+    fn treque_should_be_a_list() {
+        check_list::<Treque<i32>>();
+    }
+
+    #[test]
+    fn dl_list_should_be_a_list() {
+        check_list::<DLList<i32>>();
+    }
+
+    #[test]
+    fn se_list_should_be_a_list() {
+        check_list::<SEList<i32>>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_should_panic_out_of_bounds() {
+        let mut list = ArrayStack::new();
+        List::add(&mut list, 0, 1);
+        List::set(&mut list, 1, 2);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! See `Queue`
 
+use alloc::collections::{BinaryHeap, LinkedList, VecDeque};
+
 /// A FIFO list container
 pub trait Queue {
     type Item;
@@ -11,4 +13,181 @@ pub trait Queue {
 
     /// Remove and return the item in front of the queue
     fn remove(&mut self) -> Option<Self::Item>;
+
+    /// Get a reference to the item in front of the queue, the next one to be removed
+    fn peek(&self) -> Option<&Self::Item>;
+
+    /// The number of items in the queue
+    fn len(&self) -> usize;
+
+    /// Is the queue empty ?
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Queue for VecDeque<T> {
+    type Item = T;
+
+    fn add(&mut self, item: T) {
+        self.push_back(item)
+    }
+
+    fn remove(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+}
+
+impl<T> Queue for LinkedList<T> {
+    type Item = T;
+
+    fn add(&mut self, item: T) {
+        self.push_back(item)
+    }
+
+    fn remove(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+}
+
+/// A priority queue: the greatest item is in front of the queue
+impl<T: Ord> Queue for BinaryHeap<T> {
+    type Item = T;
+
+    fn add(&mut self, item: T) {
+        self.push(item)
+    }
+
+    fn remove(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        BinaryHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Queue;
+    use crate::array_based_lists::array_deque::ArrayDeque;
+    use crate::array_based_lists::array_queue::{ArrayQueue, RingBuffer};
+    use crate::array_based_lists::inline_array_queue::InlineArrayQueue;
+    use crate::array_based_lists::min_queue::MinQueue;
+    use crate::array_based_lists::random_queue::{RandomQueue, XorShift};
+    use crate::linked_lists::sl_list::SLList;
+    use std::collections::{BinaryHeap, LinkedList, VecDeque};
+    use std::vec::Vec;
+
+    fn check_queue<Q: Queue<Item = i32> + Default>() {
+        let mut queue = Q::default();
+        assert!(queue.is_empty());
+        assert_eq!(queue.peek(), None);
+        for i in 0..10 {
+            queue.add(i);
+            assert_eq!(queue.peek(), Some(&0));
+        }
+        assert_eq!(queue.len(), 10);
+        assert!(!queue.is_empty());
+        for i in 0..10 {
+            assert_eq!(queue.peek(), Some(&i));
+            assert_eq!(queue.remove(), Some(i));
+        }
+        assert_eq!(queue.remove(), None);
+        assert_eq!(queue.peek(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn array_queue_should_be_a_queue() {
+        check_queue::<ArrayQueue<i32>>();
+    }
+
+    #[test]
+    fn array_deque_should_be_a_queue() {
+        check_queue::<ArrayDeque<i32>>();
+    }
+
+    #[test]
+    fn inline_array_queue_should_be_a_queue() {
+        check_queue::<InlineArrayQueue<i32, 10>>();
+    }
+
+    #[test]
+    fn min_queue_should_be_a_queue() {
+        check_queue::<MinQueue<i32>>();
+    }
+
+    #[test]
+    fn ring_buffer_should_peek_the_oldest_element() {
+        let mut queue = RingBuffer::with_capacity(3);
+        for i in 0..5 {
+            Queue::add(&mut queue, i);
+        }
+        assert_eq!(Queue::len(&queue), 3);
+        assert_eq!(Queue::peek(&queue), Some(&2));
+        assert_eq!(Queue::remove(&mut queue), Some(2));
+        assert_eq!(Queue::peek(&queue), Some(&3));
+    }
+
+    #[test]
+    fn sl_list_should_be_a_queue() {
+        check_queue::<SLList<i32>>();
+    }
+
+    #[test]
+    fn vec_deque_should_be_a_queue() {
+        check_queue::<VecDeque<i32>>();
+    }
+
+    #[test]
+    fn linked_list_should_be_a_queue() {
+        check_queue::<LinkedList<i32>>();
+    }
+
+    #[test]
+    fn binary_heap_should_be_a_priority_queue() {
+        let mut queue = BinaryHeap::new();
+        for i in [3, 1, 4, 1, 5, 9, 2, 6] {
+            Queue::add(&mut queue, i);
+        }
+        assert_eq!(Queue::len(&queue), 8);
+        assert_eq!(Queue::peek(&queue), Some(&9));
+        let removed: Vec<_> = std::iter::from_fn(|| Queue::remove(&mut queue)).collect();
+        assert_eq!(removed, [9, 6, 5, 4, 3, 2, 1, 1]);
+        assert!(Queue::is_empty(&queue));
+    }
+
+    #[test]
+    fn random_queue_should_peek_the_next_removed_element() {
+        let mut queue = RandomQueue::with_source(XorShift::new(42));
+        assert_eq!(Queue::peek(&queue), None);
+        for i in 0..100 {
+            Queue::add(&mut queue, i);
+        }
+        while let Some(&next) = Queue::peek(&queue) {
+            assert_eq!(Queue::remove(&mut queue), Some(next));
+        }
+        assert!(Queue::is_empty(&queue));
+    }
 }
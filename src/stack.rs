@@ -2,6 +2,9 @@
 //!
 //! See `Stack`
 
+use alloc::collections::{LinkedList, VecDeque};
+use alloc::vec::Vec;
+
 /// A LIFO list container
 pub trait Stack {
     type Item;
@@ -11,4 +14,153 @@ pub trait Stack {
 
     /// Remove and return the item on top of the stack
     fn pop(&mut self) -> Option<Self::Item>;
+
+    /// Get a reference to the item on top of the stack
+    fn peek(&self) -> Option<&Self::Item>;
+
+    /// The number of items in the stack
+    fn len(&self) -> usize;
+
+    /// Is the stack empty ?
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Stack for Vec<T> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        Vec::push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        Vec::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.last()
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
+/// The top of the stack is the back of the deque
+impl<T> Stack for VecDeque<T> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        self.push_back(item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.back()
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+}
+
+/// The top of the stack is the back of the list
+impl<T> Stack for LinkedList<T> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        self.push_back(item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.back()
+    }
+
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stack;
+    use crate::array_based_lists::array_deque::ArrayDeque;
+    use crate::array_based_lists::array_stack::ArrayStack;
+    use crate::array_based_lists::inline_array_stack::InlineArrayStack;
+    use crate::array_based_lists::min_stack::MinStack;
+    use crate::array_based_lists::rootish_array_stack::RootishArrayStack;
+    use crate::linked_lists::sl_list::SLList;
+    use std::collections::{LinkedList, VecDeque};
+    use std::vec::Vec;
+
+    fn check_stack<S: Stack<Item = i32> + Default>() {
+        let mut stack = S::default();
+        assert!(stack.is_empty());
+        assert_eq!(stack.peek(), None);
+        for i in 0..10 {
+            stack.push(i);
+            assert_eq!(stack.peek(), Some(&i));
+        }
+        assert_eq!(stack.len(), 10);
+        assert!(!stack.is_empty());
+        for i in (0..10).rev() {
+            assert_eq!(stack.pop(), Some(i));
+        }
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.peek(), None);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn array_stack_should_be_a_stack() {
+        check_stack::<ArrayStack<i32>>();
+    }
+
+    #[test]
+    fn array_deque_should_be_a_stack() {
+        check_stack::<ArrayDeque<i32>>();
+    }
+
+    #[test]
+    fn rootish_array_stack_should_be_a_stack() {
+        check_stack::<RootishArrayStack<i32>>();
+    }
+
+    #[test]
+    fn inline_array_stack_should_be_a_stack() {
+        check_stack::<InlineArrayStack<i32, 10>>();
+    }
+
+    #[test]
+    fn min_stack_should_be_a_stack() {
+        check_stack::<MinStack<i32>>();
+    }
+
+    #[test]
+    fn sl_list_should_be_a_stack() {
+        check_stack::<SLList<i32>>();
+    }
+
+    #[test]
+    fn vec_should_be_a_stack() {
+        check_stack::<Vec<i32>>();
+    }
+
+    #[test]
+    fn vec_deque_should_be_a_stack() {
+        check_stack::<VecDeque<i32>>();
+    }
+
+    #[test]
+    fn linked_list_should_be_a_stack() {
+        check_stack::<LinkedList<i32>>();
+    }
 }
@@ -0,0 +1,455 @@
+//! Differential testing of the array-based lists against `Vec` and `VecDeque`
+//!
+//! Long random sequences of operations are run on each container and on its reference,
+//! comparing every result and the whole content after each step.
+//! On failure, the sequence is minimized and reported along with its seed.
+//!
+//! - `ODS_FUZZ_SEED=<seed>` replays a single seed
+//! - `ODS_FUZZ_ITERATIONS=<count>` changes the number of random sequences per container
+
+use opendatastructures::array_based_lists::array_deque::ArrayDeque;
+use opendatastructures::array_based_lists::array_queue::ArrayQueue;
+use opendatastructures::array_based_lists::array_stack::ArrayStack;
+use opendatastructures::array_based_lists::resize_policy::ResizePolicy;
+use opendatastructures::deque::Deque;
+use opendatastructures::stack::Stack;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::env;
+use std::fmt::Debug;
+use std::panic::{self, catch_unwind, AssertUnwindSafe, PanicHookInfo};
+use std::sync::OnceLock;
+
+const DEFAULT_ITERATIONS: u64 = 100;
+const OPERATIONS: usize = 1_000;
+
+/// An operation, with indices resolved against the length when it is applied
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Add(usize, i32),
+    Remove(usize),
+    Push(i32),
+    Pop,
+    PushFront(i32),
+    PopFront,
+    Get(usize),
+    Set(usize, i32),
+    Truncate(usize),
+    Drain(usize, usize),
+    Clear,
+    Reserve(usize),
+    ShrinkToFit,
+    Shrinking(bool),
+}
+
+type Runner = fn(&[Op]) -> Result<(), String>;
+
+/// Generate a sequence alternating between growing and shrinking phases
+fn generate(seed: u64) -> Vec<Op> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut growing = true;
+    let mut ops = Vec::with_capacity(OPERATIONS);
+    for _ in 0..OPERATIONS {
+        if rng.gen_ratio(1, 50) {
+            growing = !growing;
+        }
+        let index = rng.gen_range(0..1_000);
+        let value = rng.gen();
+        let op = match rng.gen_range(0..100) {
+            0..=29 if growing => Op::Add(index, value),
+            0..=29 => Op::Remove(index),
+            30..=44 if growing => Op::Push(value),
+            30..=44 => Op::Pop,
+            45..=59 if growing => Op::PushFront(value),
+            45..=59 => Op::PopFront,
+            60..=74 => Op::Get(index),
+            75..=89 => Op::Set(index, value),
+            90..=91 => Op::Truncate(index),
+            92..=93 => Op::Drain(index, rng.gen_range(0..1_000)),
+            94 => Op::Clear,
+            95..=96 => Op::Reserve(rng.gen_range(0..100)),
+            97 => Op::ShrinkToFit,
+            _ => Op::Shrinking(rng.gen()),
+        };
+        ops.push(op);
+    }
+    ops
+}
+
+/// The policy applied by `Op::Shrinking`
+fn policy(shrinking: bool) -> ResizePolicy {
+    if shrinking {
+        ResizePolicy::default()
+    } else {
+        ResizePolicy::default().without_shrinking()
+    }
+}
+
+/// Resolve a random `(start, end)` pair into a range of `0..len`
+fn range(start: usize, end: usize, len: usize) -> std::ops::Range<usize> {
+    let (start, end) = (start % (len + 1), end % (len + 1));
+    usize::min(start, end)..usize::max(start, end)
+}
+
+fn ensure_eq<T: Debug + PartialEq>(step: usize, actual: T, expected: T) -> Result<(), String> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "step {}: got {:?}, expected {:?}",
+            step, actual, expected
+        ))
+    }
+}
+
+fn ensure_same_content<'a>(
+    step: usize,
+    actual: impl Iterator<Item = &'a i32>,
+    expected: impl Iterator<Item = &'a i32>,
+) -> Result<(), String> {
+    ensure_eq(
+        step,
+        actual.copied().collect::<Vec<_>>(),
+        expected.copied().collect::<Vec<_>>(),
+    )
+}
+
+fn ensure_power_of_two(step: usize, capacity: usize) -> Result<(), String> {
+    if capacity == 0 || capacity.is_power_of_two() {
+        Ok(())
+    } else {
+        Err(format!(
+            "step {}: capacity {} is not a power of two",
+            step, capacity
+        ))
+    }
+}
+
+fn run_array_stack(ops: &[Op]) -> Result<(), String> {
+    let mut arr = ArrayStack::new();
+    let mut vec = Vec::new();
+    for (step, &op) in ops.iter().enumerate() {
+        let len = vec.len();
+        match op {
+            Op::Add(index, value) => {
+                arr.add(index % (len + 1), value);
+                vec.insert(index % (len + 1), value);
+            }
+            Op::Remove(index) if len > 0 => {
+                ensure_eq(step, arr.remove(index % len), vec.remove(index % len))?
+            }
+            Op::Push(value) | Op::PushFront(value) => {
+                arr.push(value);
+                vec.push(value);
+            }
+            Op::Remove(_) | Op::Pop | Op::PopFront => ensure_eq(step, arr.pop(), vec.pop())?,
+            Op::Get(index) => {
+                ensure_eq(step, arr.get(index % (len + 1)), vec.get(index % (len + 1)))?
+            }
+            Op::Set(index, value) => {
+                if let Some(element) = arr.get_mut(index % (len + 1)) {
+                    *element = value;
+                }
+                if let Some(element) = vec.get_mut(index % (len + 1)) {
+                    *element = value;
+                }
+            }
+            Op::Truncate(new_len) => {
+                arr.truncate(new_len % (len + 1));
+                vec.truncate(new_len % (len + 1));
+            }
+            Op::Drain(start, end) => {
+                let range = range(start, end, len);
+                ensure_eq(
+                    step,
+                    arr.drain(range.clone()).collect::<Vec<_>>(),
+                    vec.drain(range).collect::<Vec<_>>(),
+                )?
+            }
+            Op::Clear => {
+                arr.clear();
+                vec.clear();
+            }
+            Op::Reserve(additional) => {
+                arr.reserve(additional);
+                if arr.capacity() < len + additional {
+                    return Err(format!(
+                        "step {}: capacity {} is too small",
+                        step,
+                        arr.capacity()
+                    ));
+                }
+            }
+            Op::ShrinkToFit => arr.shrink_to_fit(),
+            Op::Shrinking(shrinking) => arr.set_resize_policy(policy(shrinking)),
+        }
+        ensure_eq(step, arr.len(), vec.len())?;
+        ensure_same_content(step, arr.iter(), vec.iter())?;
+    }
+    Ok(())
+}
+
+fn run_array_queue(ops: &[Op]) -> Result<(), String> {
+    let mut arr = ArrayQueue::new();
+    let mut deque = VecDeque::new();
+    for (step, &op) in ops.iter().enumerate() {
+        let len = deque.len();
+        match op {
+            Op::Add(_, value) | Op::Push(value) | Op::PushFront(value) | Op::Set(_, value) => {
+                arr.add(value);
+                deque.push_back(value);
+            }
+            Op::Remove(_) | Op::Pop | Op::PopFront | Op::Get(_) => {
+                ensure_eq(step, arr.remove(), deque.pop_front())?
+            }
+            Op::Truncate(new_len) => {
+                arr.truncate(new_len % (len + 1));
+                deque.truncate(new_len % (len + 1));
+            }
+            Op::Drain(start, end) => {
+                let range = range(start, end, len);
+                ensure_eq(
+                    step,
+                    arr.drain(range.clone()).collect::<Vec<_>>(),
+                    deque.drain(range).collect::<Vec<_>>(),
+                )?
+            }
+            Op::Clear => {
+                arr.clear();
+                deque.clear();
+            }
+            Op::Reserve(additional) => {
+                arr.reserve(additional);
+                if arr.capacity() < len + additional {
+                    return Err(format!(
+                        "step {}: capacity {} is too small",
+                        step,
+                        arr.capacity()
+                    ));
+                }
+            }
+            Op::ShrinkToFit => arr.shrink_to_fit(),
+            Op::Shrinking(shrinking) => arr.set_resize_policy(policy(shrinking)),
+        }
+        ensure_eq(step, arr.iter().len(), deque.len())?;
+        ensure_same_content(step, arr.iter(), deque.iter())?;
+        ensure_same_content(step, arr.iter().rev(), deque.iter().rev())?;
+        ensure_power_of_two(step, arr.capacity())?;
+    }
+    Ok(())
+}
+
+fn run_array_deque(ops: &[Op]) -> Result<(), String> {
+    let mut arr = ArrayDeque::new();
+    let mut deque = VecDeque::new();
+    for (step, &op) in ops.iter().enumerate() {
+        let len = deque.len();
+        match op {
+            Op::Add(index, value) => {
+                arr.add(index % (len + 1), value);
+                deque.insert(index % (len + 1), value);
+            }
+            Op::Remove(index) => ensure_eq(
+                step,
+                arr.remove(index % (len + 1)),
+                deque.remove(index % (len + 1)),
+            )?,
+            Op::Push(value) => {
+                arr.add_last(value);
+                deque.push_back(value);
+            }
+            Op::Pop => ensure_eq(step, arr.remove_last(), deque.pop_back())?,
+            Op::PushFront(value) => {
+                arr.add_first(value);
+                deque.push_front(value);
+            }
+            Op::PopFront => ensure_eq(step, arr.remove_first(), deque.pop_front())?,
+            Op::Get(index) => ensure_eq(
+                step,
+                arr.get(index % (len + 1)),
+                deque.get(index % (len + 1)),
+            )?,
+            Op::Set(index, value) => {
+                if let Some(element) = arr.get_mut(index % (len + 1)) {
+                    *element = value;
+                }
+                if let Some(element) = deque.get_mut(index % (len + 1)) {
+                    *element = value;
+                }
+            }
+            Op::Truncate(new_len) => {
+                arr.truncate(new_len % (len + 1));
+                deque.truncate(new_len % (len + 1));
+            }
+            Op::Drain(start, end) => {
+                let range = range(start, end, len);
+                ensure_eq(
+                    step,
+                    arr.drain(range.clone()).collect::<Vec<_>>(),
+                    deque.drain(range).collect::<Vec<_>>(),
+                )?
+            }
+            Op::Clear => {
+                arr.clear();
+                deque.clear();
+            }
+            Op::Reserve(additional) => {
+                arr.reserve(additional);
+                if arr.capacity() < len + additional {
+                    return Err(format!(
+                        "step {}: capacity {} is too small",
+                        step,
+                        arr.capacity()
+                    ));
+                }
+            }
+            Op::ShrinkToFit => arr.shrink_to_fit(),
+            Op::Shrinking(shrinking) => arr.set_resize_policy(policy(shrinking)),
+        }
+        ensure_eq(step, arr.iter().len(), deque.len())?;
+        ensure_same_content(step, arr.iter(), deque.iter())?;
+        ensure_same_content(step, arr.iter().rev(), deque.iter().rev())?;
+        ensure_power_of_two(step, arr.capacity())?;
+    }
+    Ok(())
+}
+
+/// Run the operations, turning panics into failures
+fn run(runner: Runner, ops: &[Op]) -> Result<(), String> {
+    match catch_unwind(AssertUnwindSafe(|| runner(ops))) {
+        Ok(result) => result,
+        Err(panic) => Err(match panic.downcast_ref::<String>() {
+            Some(message) => format!("panicked: {}", message),
+            None => match panic.downcast_ref::<&str>() {
+                Some(message) => format!("panicked: {}", message),
+                None => "panicked".to_string(),
+            },
+        }),
+    }
+}
+
+thread_local! {
+    static SILENT: Cell<bool> = const { Cell::new(false) };
+}
+
+type Hook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send>;
+
+/// The panic hook in place before the filtering one, which reports the panics not silenced
+static PREVIOUS_HOOK: OnceLock<Hook> = OnceLock::new();
+
+/// Run `f` without printing the messages of the panics it raises
+///
+/// The hook is shared by the threads of the test harness: a filtering hook is installed once,
+/// and only silences the panics of the threads running `silently`.
+fn silently<R>(f: impl FnOnce() -> R) -> R {
+    let mut installed = false;
+    PREVIOUS_HOOK.get_or_init(|| {
+        installed = true;
+        panic::take_hook()
+    });
+    if installed {
+        panic::set_hook(Box::new(|info| {
+            if !SILENT.with(Cell::get) {
+                if let Some(hook) = PREVIOUS_HOOK.get() {
+                    hook(info);
+                }
+            }
+        }));
+    }
+    SILENT.with(|silent| silent.set(true));
+    let result = f();
+    SILENT.with(|silent| silent.set(false));
+    result
+}
+
+/// Remove as many operations as possible while still failing
+///
+/// The candidate sequences are run silently, so that the report is not buried under
+/// the messages of their panics.
+fn minimize(runner: Runner, mut ops: Vec<Op>) -> Vec<Op> {
+    silently(|| {
+        let mut chunk = ops.len() / 2;
+        while chunk > 0 {
+            let mut start = 0;
+            while start < ops.len() {
+                let end = usize::min(start + chunk, ops.len());
+                let mut candidate = ops[..start].to_vec();
+                candidate.extend_from_slice(&ops[end..]);
+                if run(runner, &candidate).is_err() {
+                    ops = candidate;
+                } else {
+                    start = end;
+                }
+            }
+            chunk /= 2;
+        }
+        ops
+    })
+}
+
+fn seeds() -> Vec<u64> {
+    if let Ok(seed) = env::var("ODS_FUZZ_SEED") {
+        return vec![seed.parse().expect("ODS_FUZZ_SEED should be an integer")];
+    }
+    let iterations = env::var("ODS_FUZZ_ITERATIONS")
+        .map(|iterations| {
+            iterations
+                .parse()
+                .expect("ODS_FUZZ_ITERATIONS should be an integer")
+        })
+        .unwrap_or(DEFAULT_ITERATIONS);
+    (0..iterations).collect()
+}
+
+fn fuzz(name: &str, runner: Runner) {
+    for seed in seeds() {
+        let ops = generate(seed);
+        if run(runner, &ops).is_err() {
+            let ops = minimize(runner, ops);
+            let error = run(runner, &ops).expect_err("minimized sequence should fail");
+            panic!(
+                "{} diverged from its reference with seed {} (replay with ODS_FUZZ_SEED={})\n\
+                 {}\nminimized sequence of {} operations:\n{:#?}",
+                name,
+                seed,
+                seed,
+                error,
+                ops.len(),
+                ops
+            );
+        }
+    }
+}
+
+#[test]
+fn array_stack_should_behave_like_vec() {
+    fuzz("ArrayStack", run_array_stack);
+}
+
+#[test]
+fn array_queue_should_behave_like_vec_deque() {
+    fuzz("ArrayQueue", run_array_queue);
+}
+
+#[test]
+fn array_deque_should_behave_like_vec_deque() {
+    fuzz("ArrayDeque", run_array_deque);
+}
+
+#[test]
+fn minimize_should_keep_a_failing_sequence() {
+    // Fails as soon as 3 elements are pushed, whatever the other operations
+    fn runner(ops: &[Op]) -> Result<(), String> {
+        let pushes = ops.iter().filter(|op| matches!(op, Op::Push(_))).count();
+        assert!(pushes < 3, "too many pushes");
+        Ok(())
+    }
+    let ops = generate(0);
+    assert!(run(runner, &ops).is_err());
+    let minimized = minimize(runner, ops);
+    assert!(!SILENT.with(Cell::get));
+    assert_eq!(minimized.len(), 3);
+    assert!(minimized.iter().all(|op| matches!(op, Op::Push(_))));
+}
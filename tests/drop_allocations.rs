@@ -0,0 +1,117 @@
+//! Dropping a container must release its storage without reallocating it,
+//! and the inline containers must never allocate.
+//!
+//! Allocations are counted per thread by a global allocator, so the allocations of the test
+//! harness are not counted.
+
+use opendatastructures::array_based_lists::array_deque::ArrayDeque;
+use opendatastructures::array_based_lists::array_queue::ArrayQueue;
+use opendatastructures::array_based_lists::array_stack::ArrayStack;
+use opendatastructures::array_based_lists::dual_array_deque::DualArrayDeque;
+use opendatastructures::array_based_lists::inline_array_queue::InlineArrayQueue;
+use opendatastructures::array_based_lists::inline_array_stack::InlineArrayStack;
+use opendatastructures::array_based_lists::rootish_array_stack::RootishArrayStack;
+use opendatastructures::linked_lists::dl_list::DLList;
+use opendatastructures::linked_lists::se_list::SEList;
+use opendatastructures::linked_lists::sl_list::SLList;
+use opendatastructures::linked_lists::xor_list::XorList;
+use opendatastructures::queue::Queue;
+use opendatastructures::stack::Stack;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The number of allocations made by the current thread
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Count the allocations made while dropping given value
+fn allocations_on_drop<T>(value: T) -> usize {
+    let before = allocations();
+    drop(value);
+    allocations() - before
+}
+
+#[test]
+fn should_not_allocate_on_drop() {
+    const LEN: usize = 10_000;
+
+    let arr: ArrayStack<_> = (0..LEN).map(Box::new).collect();
+    assert_eq!(allocations_on_drop(arr), 0);
+
+    let arr: ArrayQueue<_> = (0..LEN).map(Box::new).collect();
+    assert_eq!(allocations_on_drop(arr), 0);
+
+    let arr: ArrayDeque<_> = (0..LEN).map(Box::new).collect();
+    assert_eq!(allocations_on_drop(arr), 0);
+
+    let arr: DualArrayDeque<_> = (0..LEN).map(Box::new).collect();
+    assert_eq!(allocations_on_drop(arr), 0);
+
+    let mut arr = RootishArrayStack::new();
+    for i in 0..LEN {
+        arr.push(Box::new(i));
+    }
+    assert_eq!(allocations_on_drop(arr), 0);
+
+    let list: SLList<_> = (0..LEN).map(Box::new).collect();
+    assert_eq!(allocations_on_drop(list), 0);
+
+    let list: DLList<_> = (0..LEN).map(Box::new).collect();
+    assert_eq!(allocations_on_drop(list), 0);
+
+    let list: SEList<_> = (0..LEN).map(Box::new).collect();
+    assert_eq!(allocations_on_drop(list), 0);
+
+    let list: XorList<_> = (0..LEN).map(Box::new).collect();
+    assert_eq!(allocations_on_drop(list), 0);
+
+    // Clearing, truncating and draining keep the storage as well
+    let mut arr: ArrayDeque<_> = (0..LEN).map(Box::new).collect();
+    let before = allocations();
+    arr.drain(10..LEN / 2);
+    arr.truncate(20);
+    arr.clear();
+    assert_eq!(allocations() - before, 0);
+
+    // Inline containers keep their elements in place
+    let before = allocations();
+    let mut stack = InlineArrayStack::<_, 64>::new();
+    let mut queue = InlineArrayQueue::<_, 64>::new();
+    for i in 0..LEN {
+        if stack.try_push(i).is_err() {
+            stack.clear();
+        }
+        if queue.try_add(i).is_err() {
+            Queue::remove(&mut queue);
+        }
+    }
+    drop(stack);
+    drop(queue);
+    assert_eq!(allocations() - before, 0);
+}
@@ -0,0 +1,23 @@
+//! The library must build without the `std` feature, only requiring the `alloc` crate.
+
+use std::env;
+use std::process::Command;
+
+#[test]
+fn should_build_without_std() {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["check", "--lib", "--no-default-features", "--quiet"])
+        .arg("--manifest-path")
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        // A dedicated target directory avoids waiting for the lock held by the running build
+        .arg("--target-dir")
+        .arg(concat!(env!("CARGO_TARGET_TMPDIR"), "/no_std"))
+        .output()
+        .expect("failed to run cargo");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}